@@ -0,0 +1,42 @@
+use winit::event_loop;
+use wzui::{App, egui};
+
+fn main() {
+    let event_loop = event_loop::EventLoop::new().unwrap();
+    let mut app = App::default();
+
+    // 一个简单的设置面板：主题和缩放作用于 egui 上下文，标题通过窗口命令修改窗口
+    let mut scale = 1.0_f32;
+    let mut title = String::from("wzui");
+    let mut applied_title = String::new();
+    app.set_ui(move |ctx| {
+        egui::Window::new("Settings").show(ctx, |ui| {
+            let mut dark_mode = ctx.style().visuals.dark_mode;
+            if ui.checkbox(&mut dark_mode, "Dark mode").changed() {
+                ctx.set_visuals(if dark_mode {
+                    egui::Visuals::dark()
+                } else {
+                    egui::Visuals::light()
+                });
+            }
+            // 拖动过程中缩放会移动滑块本身，松开后再应用
+            let slider = ui.add(egui::Slider::new(&mut scale, 0.5..=2.0).text("Scale"));
+            if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                ctx.set_zoom_factor(scale);
+            }
+            ui.horizontal(|ui| {
+                ui.label("Title");
+                ui.text_edit_singleline(&mut title);
+            });
+            // 第一帧和每次编辑后把标题应用到窗口
+            if title != applied_title {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+                applied_title.clone_from(&title);
+            }
+            ui.separator();
+            ui.label(format!("FPS: {:.0}", 1.0 / ctx.input(|i| i.stable_dt)));
+        });
+    });
+
+    event_loop.run_app(&mut app).unwrap();
+}
@@ -0,0 +1,219 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use wgpu::SurfaceError;
+use winit::{
+    application::ApplicationHandler,
+    event::{DeviceEvent, DeviceId, KeyEvent},
+    event_loop::{ActiveEventLoop, ControlFlow},
+    keyboard::{Key, NamedKey},
+    window::{Window, WindowAttributes},
+};
+
+#[cfg(feature = "egui")]
+use crate::egui_layer::EguiLayer;
+use crate::{KeyFilter, Renderer, RendererConfig};
+
+/// 应用配置
+#[derive(Debug, Clone, Default)]
+pub struct AppConfig {
+    /// 最大帧率。`None` 表示不限制：每帧结束后立即请求重绘，只由 VSync 节流；
+    /// 设置后事件循环在两帧之间用 `ControlFlow::WaitUntil` 休眠，而不是空转。
+    pub max_fps: Option<u32>,
+    /// 创建 Renderer 时使用的配置
+    pub renderer: RendererConfig,
+    /// 创建窗口后立即显示。默认 `false`：窗口先以隐藏状态创建，
+    /// 第一帧呈现之后再显示，避免启动时闪过一帧白屏或未初始化的内容。
+    pub show_immediately: bool,
+    /// 哪些按键事件会触发快捷键（`App::set_on_key_press` 和 Escape 释放鼠标）
+    pub key_filter: KeyFilter,
+}
+
+impl AppConfig {
+    // 两帧之间的目标间隔
+    fn frame_interval(&self) -> Option<Duration> {
+        self.max_fps
+            .filter(|&fps| fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+}
+
+/// 快捷键回调
+type KeyPressCallback = Box<dyn FnMut(&mut Renderer, &KeyEvent)>;
+
+#[derive(Default)]
+pub struct App {
+    config: AppConfig,
+    on_key_press: Option<KeyPressCallback>,
+    window: Option<Arc<Window>>,
+    renderer: Option<Renderer>,
+    next_frame: Option<Instant>,
+    // 窗口被完全遮挡时暂停渲染，见 WindowEvent::Occluded
+    occluded: bool,
+    #[cfg(feature = "egui")]
+    egui: Option<EguiLayer>,
+}
+
+impl App {
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// 注册快捷键回调，只对通过 `AppConfig::key_filter` 的按下事件调用
+    pub fn set_on_key_press(&mut self, callback: impl FnMut(&mut Renderer, &KeyEvent) + 'static) {
+        self.on_key_press = Some(Box::new(callback));
+    }
+
+    /// 设置每帧运行的 egui UI 闭包，UI 会叠加绘制在场景之上。
+    ///
+    /// 闭包中可以用 `ctx.send_viewport_cmd` 操作窗口，例如
+    /// `egui::ViewportCommand::Title` 修改标题。
+    #[cfg(feature = "egui")]
+    pub fn set_ui(&mut self, ui: impl FnMut(&egui::Context) + 'static) {
+        let mut layer = EguiLayer::new(Box::new(ui));
+        if let (Some(window), Some(renderer)) = (self.window.as_ref(), self.renderer.as_ref()) {
+            layer.init(window, renderer.max_texture_side());
+        }
+        self.egui = Some(layer);
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_none() {
+            let attributes = WindowAttributes::default().with_visible(self.config.show_immediately);
+            let window = Arc::new(event_loop.create_window(attributes).unwrap());
+            self.window = Some(window.clone());
+            let mut renderer = pollster::block_on(Renderer::with_config(
+                window.clone(),
+                self.config.renderer.clone(),
+            ));
+            if !self.config.show_immediately {
+                // 部分平台不会给隐藏的窗口发送 RedrawRequested，这里直接画第一帧
+                renderer.set_wait_for_first_frame(true);
+                if let Err(e) = renderer.render() {
+                    eprintln!("Error rendering first frame: {:?}", e);
+                    window.set_visible(true);
+                }
+            }
+            #[cfg(feature = "egui")]
+            if let Some(egui) = self.egui.as_mut() {
+                egui.init(&window, renderer.max_texture_side());
+            }
+            self.renderer = Some(renderer);
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: winit::window::WindowId,
+        event: winit::event::WindowEvent,
+    ) {
+        let (Some(window), Some(renderer)) = (self.window.as_mut(), self.renderer.as_mut()) else {
+            return;
+        };
+
+        if window_id != window.id() {
+            return;
+        }
+
+        // 先把事件交给 egui；被 egui 消费的输入事件不再向下传递
+        #[cfg(feature = "egui")]
+        if let Some(egui) = self.egui.as_mut()
+            && egui.on_window_event(window, &event)
+        {
+            return;
+        }
+
+        match event {
+            winit::event::WindowEvent::CloseRequested => event_loop.exit(),
+            winit::event::WindowEvent::Resized(new_size) => renderer.resize(new_size),
+            // 失去焦点或按下 Escape 时释放鼠标捕获
+            winit::event::WindowEvent::Focused(false) if renderer.cursor_grabbed() => {
+                renderer.set_cursor_grab(false)
+            }
+            winit::event::WindowEvent::KeyboardInput {
+                event,
+                is_synthetic,
+                ..
+            } if self.config.key_filter.accepts(&event, is_synthetic) => {
+                if renderer.cursor_grabbed() && event.logical_key == Key::Named(NamedKey::Escape) {
+                    renderer.set_cursor_grab(false);
+                }
+                if let Some(on_key_press) = self.on_key_press.as_mut() {
+                    on_key_press(renderer, &event);
+                }
+            }
+            // 被遮挡时停止重绘以节省功耗；重新可见时立即画一帧刷新内容。
+            // 只有部分平台（macOS、Wayland、Windows）会发送该事件
+            winit::event::WindowEvent::Occluded(occluded) => {
+                self.occluded = occluded;
+                if occluded {
+                    self.next_frame = None;
+                } else {
+                    window.request_redraw();
+                }
+            }
+            // 遮挡期间不再续订下一帧，重绘链在此中断
+            winit::event::WindowEvent::RedrawRequested if self.occluded => {}
+            winit::event::WindowEvent::RedrawRequested => {
+                match self.config.frame_interval() {
+                    // 逐帧模式只在 step_frame 或窗口需要刷新时重绘，同时取消
+                    // frame_interval 预约的下一帧，about_to_wait 随之改为无限期等待
+                    _ if renderer.fixed_step().is_some() => self.next_frame = None,
+                    // 等到下一帧的时间点再重绘，见 about_to_wait
+                    Some(interval) => self.next_frame = Some(Instant::now() + interval),
+                    None => window.request_redraw(), // 确保在下一次循环时再次触发重绘
+                }
+                #[cfg(feature = "egui")]
+                if let Some(frame) = self.egui.as_mut().and_then(|egui| egui.run(window)) {
+                    renderer.set_egui_frame(frame);
+                }
+                match renderer.render() {
+                    Err(SurfaceError::Lost | SurfaceError::OutOfMemory) => event_loop.exit(),
+                    Err(e) => eprintln!("Error rendering: {:?}", e),
+                    Ok(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let (Some(renderer), DeviceEvent::MouseMotion { delta }) =
+            (self.renderer.as_mut(), event)
+        {
+            renderer.handle_mouse_motion(delta);
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        // 没有预约的下一帧（遮挡、逐帧模式）时改回无限期等待，
+        // 否则过期的 WaitUntil 会让事件循环以零超时空转
+        let Some(next_frame) = self.next_frame else {
+            event_loop.set_control_flow(ControlFlow::Wait);
+            return;
+        };
+
+        // 输入事件同样会唤醒循环；到点后才请求重绘，否则继续休眠到下一帧
+        if Instant::now() >= next_frame {
+            window.request_redraw();
+        } else {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame));
+        }
+    }
+}
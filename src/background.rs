@@ -0,0 +1,49 @@
+use wgpu::{Color, Device, Queue, RenderPass, TextureFormat};
+
+use crate::fullscreen::FullscreenPainter;
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Gradient {
+    top: [f32; 4],
+    bottom: [f32; 4],
+}
+
+fn to_array(color: Color) -> [f32; 4] {
+    [color.r, color.g, color.b, color.a].map(|c| c as f32)
+}
+
+// 在场景之前绘制全屏渐变背景，不测试也不写入深度
+pub(crate) struct BackgroundPainter(FullscreenPainter);
+
+impl BackgroundPainter {
+    pub(crate) fn new(device: &Device, format: TextureFormat, depth_format: TextureFormat) -> Self {
+        Self(FullscreenPainter::new(
+            device,
+            "Background",
+            include_str!("background.wgsl"),
+            size_of::<Gradient>(),
+            wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            },
+            depth_format,
+        ))
+    }
+
+    // 在渲染通道的最开始调用，颜色需已按目标格式编码
+    pub(crate) fn paint(
+        &self,
+        queue: &Queue,
+        render_pass: &mut RenderPass,
+        [top, bottom]: [Color; 2],
+    ) {
+        let gradient = Gradient {
+            top: to_array(top),
+            bottom: to_array(bottom),
+        };
+        self.0
+            .paint(queue, render_pass, bytemuck::bytes_of(&gradient));
+    }
+}
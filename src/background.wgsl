@@ -0,0 +1,28 @@
+// 渐变背景：用一个覆盖全屏的三角形绘制，不需要顶点缓冲区
+
+struct Gradient {
+    top: vec4<f32>,    // 线性空间（手动 sRGB 模式下已在 CPU 端编码）
+    bottom: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> gradient: Gradient;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) t: f32, // 0 为底部，1 为顶部
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // 顶点 0/1/2 -> uv (0,0)/(2,0)/(0,2)，三角形覆盖整个 [-1, 1] 区域
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv * 2.0 - 1.0, 1.0, 1.0);
+    out.t = uv.y;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return mix(gradient.bottom, gradient.top, in.t);
+}
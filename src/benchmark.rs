@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// 一组帧时间的统计（毫秒）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub p99_ms: f64,
+}
+
+impl FrameStats {
+    pub(crate) fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+
+        // 最近秩法求 p99
+        let p99_index = ((ms.len() as f64 * 0.99).ceil() as usize).clamp(1, ms.len()) - 1;
+        Self {
+            min_ms: ms[0],
+            avg_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+            max_ms: ms[ms.len() - 1],
+            p99_ms: ms[p99_index],
+        }
+    }
+}
+
+/// `Renderer::benchmark` 的结果，可以用 `{:?}` 输出保存为基线
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkReport {
+    pub frames: u32,
+    /// `render()` 在 CPU 上花费的时间（编码与提交）
+    pub cpu: FrameStats,
+    /// 从开始编码到 GPU 执行完毕的完整帧时间，包含 GPU 耗时
+    pub frame: FrameStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: impl IntoIterator<Item = u64>) -> Vec<Duration> {
+        values.into_iter().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn p99_uses_nearest_rank() {
+        // 乱序输入，排序后第 ceil(200 * 0.99) = 198 个样本
+        let stats = FrameStats::from_samples(&millis((1..=200).rev()));
+        assert_eq!(stats.min_ms, 1.0);
+        assert_eq!(stats.max_ms, 200.0);
+        assert_eq!(stats.avg_ms, 100.5);
+        assert_eq!(stats.p99_ms, 198.0);
+
+        // 样本少于 100 个时 p99 就是最大值
+        let stats = FrameStats::from_samples(&millis([5, 1, 3]));
+        assert_eq!(stats.p99_ms, 5.0);
+    }
+
+    #[test]
+    fn empty_samples_are_zero() {
+        assert_eq!(FrameStats::from_samples(&[]), FrameStats::default());
+    }
+}
@@ -0,0 +1,238 @@
+use wgpu::{
+    BindGroupLayout, BlendState, Color, CommandEncoder, Device, LoadOp, Operations,
+    PipelineCompilationOptions, PipelineLayout, RenderPassColorAttachment, RenderPassDescriptor,
+    RenderPipeline, Sampler, ShaderModule, TextureFormat, TextureView, util::DeviceExt,
+};
+
+// 一次纹理绘制
+pub(crate) struct BlitDraw {
+    pub(crate) view: TextureView,
+    // 目标矩形 [x, y, 宽, 高]，物理像素，原点在左上角
+    pub(crate) rect: [f32; 4],
+    // 采样的纹理区域 [u, v, 宽, 高]，归一化坐标
+    pub(crate) uv_rect: [f32; 4],
+    // 与采样结果相乘的颜色（线性空间）
+    pub(crate) tint: Color,
+    // false 时直接覆盖目标像素（调试视图），true 时按 alpha 混合（精灵）
+    pub(crate) blend: bool,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct BlitUniform {
+    uv_rect: [f32; 4],
+    tint: [f32; 4],
+}
+
+// 把任意颜色纹理绘制到目标的一个矩形区域中，用于在屏幕角落查看中间结果
+pub(crate) struct BlitPainter {
+    pipeline: RenderPipeline,
+    blend_pipeline: RenderPipeline,
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl BlitPainter {
+    pub(crate) fn new(device: &Device, format: TextureFormat, manual_srgb: bool) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Blit Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                concat!(include_str!("srgb.wgsl"), include_str!("blit.wgsl")).into(),
+            ),
+        });
+
+        // 不可过滤的浮点采样类型同时兼容可过滤格式和 R32Float 等格式
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Blit Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // 最近邻采样，调试时能看清每个像素
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Blit Sampler"),
+            ..Default::default()
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Blit Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = create_pipeline(
+            device,
+            &shader,
+            &pipeline_layout,
+            format,
+            manual_srgb,
+            BlendState::REPLACE,
+        );
+        let blend_pipeline = create_pipeline(
+            device,
+            &shader,
+            &pipeline_layout,
+            format,
+            manual_srgb,
+            BlendState::ALPHA_BLENDING,
+        );
+
+        Self {
+            pipeline,
+            blend_pipeline,
+            layout,
+            sampler,
+        }
+    }
+
+    // 在场景之后把排队的纹理依次绘制到 `view` 上（保留已有内容）
+    pub(crate) fn paint(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        [width, height]: [u32; 2],
+        draws: &[BlitDraw],
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("Blit Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        for draw in draws {
+            let [x, y, w, h] = draw.rect;
+            // 视口必须位于目标之内，超出的部分被裁掉，纹理区域按相同比例裁剪
+            let x0 = x.clamp(0.0, width as f32);
+            let y0 = y.clamp(0.0, height as f32);
+            let x1 = (x + w).clamp(0.0, width as f32);
+            let y1 = (y + h).clamp(0.0, height as f32);
+            if x1 <= x0 || y1 <= y0 {
+                continue;
+            }
+            let [u, v, uw, vh] = draw.uv_rect;
+            let uv_rect = [
+                u + (x0 - x) / w * uw,
+                v + (y0 - y) / h * vh,
+                (x1 - x0) / w * uw,
+                (y1 - y0) / h * vh,
+            ];
+            let tint = draw.tint;
+            let uniform = BlitUniform {
+                uv_rect,
+                tint: [tint.r as f32, tint.g as f32, tint.b as f32, tint.a as f32],
+            };
+            let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Blit Uniform Buffer"),
+                contents: bytemuck::bytes_of(&uniform),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Blit Bind Group"),
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&draw.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+            render_pass.set_pipeline(if draw.blend {
+                &self.blend_pipeline
+            } else {
+                &self.pipeline
+            });
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.set_viewport(x0, y0, x1 - x0, y1 - y0, 0.0, 1.0);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    shader: &ShaderModule,
+    layout: &PipelineLayout,
+    format: TextureFormat,
+    manual_srgb: bool,
+    blend: BlendState,
+) -> RenderPipeline {
+    let constants: &[(&str, f64)] = if manual_srgb {
+        &[("MANUAL_SRGB", 1.0)]
+    } else {
+        &[]
+    };
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Blit Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: PipelineCompilationOptions {
+                constants,
+                ..Default::default()
+            },
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
@@ -0,0 +1,35 @@
+// 把纹理的一块区域绘制到视口内：视口即目标矩形，全屏三角形铺满视口
+
+@group(0) @binding(0) var r_texture: texture_2d<f32>;
+@group(0) @binding(1) var r_sampler: sampler;
+
+struct Blit {
+    // 采样区域 [u, v, 宽, 高]
+    uv_rect: vec4<f32>,
+    tint: vec4<f32>,
+};
+@group(0) @binding(2) var<uniform> r_blit: Blit;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    // 纹理坐标的 v 轴朝下，与 NDC 的 y 轴相反
+    out.clip_position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = r_blit.uv_rect.xy + uv * r_blit.uv_rect.zw;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(r_texture, r_sampler, in.uv) * r_blit.tint;
+    if MANUAL_SRGB {
+        return vec4<f32>(srgb_from_linear(color.rgb), color.a);
+    }
+    return color;
+}
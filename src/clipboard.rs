@@ -0,0 +1,65 @@
+use std::{borrow::Cow, fmt};
+
+use wgpu::TextureFormat;
+
+/// `Renderer::copy_frame_to_clipboard` 的错误
+#[derive(Debug)]
+pub enum ClipboardError {
+    /// 渲染目标不是 8 位 RGBA/BGRA 格式，无法直接转换为剪贴板图像
+    UnsupportedFormat(TextureFormat),
+    /// 访问系统剪贴板失败
+    Clipboard(arboard::Error),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "unsupported render target format for clipboard: {format:?}"
+                )
+            }
+            Self::Clipboard(e) => write!(f, "clipboard error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnsupportedFormat(_) => None,
+            Self::Clipboard(e) => Some(e),
+        }
+    }
+}
+
+impl From<arboard::Error> for ClipboardError {
+    fn from(e: arboard::Error) -> Self {
+        Self::Clipboard(e)
+    }
+}
+
+// 把读回的像素转换为 RGBA8 后放到剪贴板
+pub(crate) fn set_image(
+    mut pixels: Vec<u8>,
+    [width, height]: [u32; 2],
+    format: TextureFormat,
+) -> Result<(), ClipboardError> {
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {}
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        _ => return Err(ClipboardError::UnsupportedFormat(format)),
+    }
+
+    arboard::Clipboard::new()?.set_image(arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: Cow::Owned(pixels),
+    })?;
+    Ok(())
+}
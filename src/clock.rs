@@ -0,0 +1,67 @@
+use std::time::Instant;
+
+// 动画时钟：所有随时间变化的效果都从这里取时间，
+// 时间缩放作用在累加上，因此所有效果一起变速。
+// 设置固定步长后不再跟随真实时间，只在 step() 时前进，用于逐帧调试和确定性测试
+pub(crate) struct AnimationClock {
+    last_tick: Option<Instant>,
+    elapsed: f32,
+    time_scale: f32,
+    fixed_step: Option<f32>,
+}
+
+impl AnimationClock {
+    pub(crate) fn new() -> Self {
+        Self {
+            last_tick: None,
+            elapsed: 0.0,
+            time_scale: 1.0,
+            fixed_step: None,
+        }
+    }
+
+    // 每帧调用一次，返回缩放后的帧间隔（秒）
+    pub(crate) fn tick(&mut self) -> f32 {
+        if self.fixed_step.is_some() {
+            // 退出固定步长模式后的第一帧从 0 开始计时，不把暂停期间的时间算进去
+            self.last_tick = None;
+            return 0.0;
+        }
+        let now = Instant::now();
+        let real_dt = self
+            .last_tick
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f32());
+        self.last_tick = Some(now);
+
+        let dt = real_dt * self.time_scale;
+        self.elapsed += dt;
+        dt
+    }
+
+    pub(crate) fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub(crate) fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub(crate) fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    pub(crate) fn fixed_step(&self) -> Option<f32> {
+        self.fixed_step
+    }
+
+    pub(crate) fn set_fixed_step(&mut self, step: Option<f32>) {
+        self.fixed_step = step.map(|dt| dt.max(0.0));
+    }
+
+    // 固定步长模式下前进一步，返回缩放后的步长；否则不做任何事
+    pub(crate) fn step(&mut self) -> f32 {
+        let dt = self.fixed_step.unwrap_or(0.0) * self.time_scale;
+        self.elapsed += dt;
+        dt
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use wgpu::Color;
+
+/// 十六进制颜色字符串格式错误，由 `Renderer::set_clear_color_hex` 返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// 去掉 `#` 后不是 6 位或 8 位
+    InvalidLength(usize),
+    /// 含有非十六进制字符
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "expected 6 or 8 hex digits, found {len}")
+            }
+            Self::InvalidDigit(c) => write!(f, "invalid hex digit {c:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+// 线性分量 -> sRGB 编码后的分量
+pub(crate) fn srgb_from_linear(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// sRGB 编码的分量 -> 线性分量
+pub(crate) fn linear_from_srgb(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+// 解析 `#RRGGBB` / `#RRGGBBAA`（`#` 可省略，不区分大小写），
+// RGB 按 sRGB 解释并转换到线性空间，alpha 保持不变
+pub(crate) fn parse_hex_color(hex: &str) -> Result<Color, ParseColorError> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(ParseColorError::InvalidDigit(c));
+    }
+    if digits.len() != 6 && digits.len() != 8 {
+        return Err(ParseColorError::InvalidLength(digits.len()));
+    }
+    // 上面已经检查过全是 ASCII 十六进制数字，按字节切分是安全的
+    let channel =
+        |i: usize| u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).unwrap() as f64 / 255.0;
+    Ok(Color {
+        r: linear_from_srgb(channel(0)),
+        g: linear_from_srgb(channel(1)),
+        b: linear_from_srgb(channel(2)),
+        a: if digits.len() == 8 { channel(3) } else { 1.0 },
+    })
+}
+
+// 手动 sRGB 模式下清屏颜色也要编码，硬件不会替我们做
+pub(crate) fn encode_srgb(color: Color) -> Color {
+    Color {
+        r: srgb_from_linear(color.r),
+        g: srgb_from_linear(color.g),
+        b: srgb_from_linear(color.b),
+        a: color.a,
+    }
+}
+
+// HSV -> RGB，h/s/v 均在 [0, 1] 内
+pub(crate) fn hsv_to_rgb(h: f64, s: f64, v: f64) -> Color {
+    let h = h.rem_euclid(1.0) * 6.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    Color {
+        r: r + m,
+        g: g + m,
+        b: b + m,
+        a: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_color_formats() {
+        let red = parse_hex_color("#FF0000").unwrap();
+        assert_eq!(parse_hex_color("ff0000"), Ok(red));
+        assert_eq!((red.r, red.g, red.b, red.a), (1.0, 0.0, 0.0, 1.0));
+
+        let translucent = parse_hex_color("#00000080").unwrap();
+        assert_eq!(translucent.a, 128.0 / 255.0);
+    }
+
+    #[test]
+    fn parse_hex_color_errors() {
+        assert_eq!(
+            parse_hex_color("#12345"),
+            Err(ParseColorError::InvalidLength(5))
+        );
+        assert_eq!(
+            parse_hex_color("#1234567"),
+            Err(ParseColorError::InvalidLength(7))
+        );
+        assert_eq!(
+            parse_hex_color("#12345g"),
+            Err(ParseColorError::InvalidDigit('g'))
+        );
+    }
+
+    #[test]
+    fn parse_hex_color_converts_to_linear() {
+        // sRGB 的 50% 灰约为线性空间的 21.6%，alpha 不做转换
+        let gray = parse_hex_color("#80808080").unwrap();
+        assert!((gray.r - 0.2158605).abs() < 1e-6);
+        assert_eq!(gray.a, 128.0 / 255.0);
+        assert!((srgb_from_linear(gray.r) - 128.0 / 255.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use wgpu::{
+    Adapter, Device, DeviceDescriptor, Features, Instance, InstanceDescriptor, InstanceFlags,
+    MemoryHints, Queue, RequestAdapterOptions,
+};
+
+use crate::renderer::RendererError;
+
+// 诊断报告中列出的可选特性
+const REPORTED_FEATURES: &[(&str, Features)] = &[
+    ("push constants", Features::PUSH_CONSTANTS),
+    ("polygon mode line", Features::POLYGON_MODE_LINE),
+    ("polygon mode point", Features::POLYGON_MODE_POINT),
+    ("timestamp query", Features::TIMESTAMP_QUERY),
+    ("texture compression BC", Features::TEXTURE_COMPRESSION_BC),
+    (
+        "texture compression ETC2",
+        Features::TEXTURE_COMPRESSION_ETC2,
+    ),
+    (
+        "texture compression ASTC",
+        Features::TEXTURE_COMPRESSION_ASTC,
+    ),
+    ("depth32float stencil8", Features::DEPTH32FLOAT_STENCIL8),
+];
+
+/// 可以在多个 `Renderer` 之间共享的 GPU 设备与队列。
+///
+/// wgpu 的句柄内部都是引用计数的，克隆只增加引用计数。用同一个上下文创建的
+/// Renderer 共用一个 `Device`/`Queue`，各自只持有自己的表面、配置和管线，
+/// 因此在一个窗口中创建的纹理、缓冲区可以直接在另一个窗口中使用。
+#[derive(Debug, Clone)]
+pub struct DeviceContext {
+    pub(crate) instance: Instance,
+    pub(crate) adapter: Adapter,
+    pub(crate) device: Device,
+    pub(crate) queue: Queue,
+}
+
+impl DeviceContext {
+    /// 使用默认实例标志创建：调试构建开启校验层，发布构建关闭；
+    /// 可以用环境变量覆盖，例如 `WGPU_VALIDATION=1`、`WGPU_DEBUG=1`
+    pub async fn new() -> Self {
+        Self::with_instance_flags(InstanceFlags::default().with_env()).await
+    }
+
+    /// 使用指定的实例标志创建，例如 `InstanceFlags::debugging()` 在发布构建中
+    /// 也开启校验层，以捕获 API 误用。环境变量不会覆盖这里给出的标志
+    pub async fn with_instance_flags(flags: InstanceFlags) -> Self {
+        match Self::try_with_instance_flags(flags).await {
+            Ok(context) => context,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    // 同 with_instance_flags，但把找不到适配器或创建设备失败作为错误返回
+    pub(crate) async fn try_with_instance_flags(
+        flags: InstanceFlags,
+    ) -> Result<Self, RendererError> {
+        let instance = Instance::new(&InstanceDescriptor {
+            flags,
+            ..Default::default()
+        });
+
+        let adapter = instance
+            .request_adapter(&RequestAdapterOptions::default())
+            .await?;
+
+        let (device, queue) = adapter
+            .request_device(&DeviceDescriptor {
+                label: Some("Device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+                memory_hints: MemoryHints::Performance,
+                trace: wgpu::Trace::Off,
+            })
+            .await?;
+
+        Ok(Self {
+            instance,
+            adapter,
+            device,
+            queue,
+        })
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+
+    /// 适配器支持的全部特性。设备创建时没有请求可选特性，
+    /// 这里反映的是硬件能力而不是当前设备已启用的特性
+    pub fn adapter_features(&self) -> Features {
+        self.adapter.features()
+    }
+
+    /// 适配器信息和常用可选特性是否受支持的文本报告，适合附在缺陷报告中
+    pub fn adapter_report(&self) -> String {
+        let info = self.adapter.get_info();
+        let features = self.adapter.features();
+        let mut report = format!(
+            "{} ({:?}, {:?}, driver: {} {})\n",
+            info.name, info.backend, info.device_type, info.driver, info.driver_info
+        );
+        for (name, feature) in REPORTED_FEATURES {
+            let supported = if features.contains(*feature) {
+                "yes"
+            } else {
+                "no"
+            };
+            let _ = writeln!(report, "  {name}: {supported}");
+        }
+        report
+    }
+}
@@ -0,0 +1,50 @@
+use wgpu::{Adapter, Device, Texture, TextureFormat, TextureUsages};
+
+pub(crate) const DEFAULT_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+// 检查深度格式能否在该设备上用作深度附件，不能时退回默认格式
+pub(crate) fn validate_depth_format(
+    adapter: &Adapter,
+    device: &Device,
+    format: TextureFormat,
+) -> TextureFormat {
+    let supported = format.has_depth_aspect()
+        && device.features().contains(format.required_features())
+        && adapter
+            .get_texture_format_features(format)
+            .allowed_usages
+            .contains(TextureUsages::RENDER_ATTACHMENT);
+    if supported {
+        format
+    } else {
+        eprintln!(
+            "Depth format {format:?} is not supported, falling back to {DEFAULT_DEPTH_FORMAT:?}"
+        );
+        DEFAULT_DEPTH_FORMAT
+    }
+}
+
+// 创建与渲染目标同尺寸的深度纹理，尺寸变化时需要重建。
+// 带 COPY_SRC 用途，以便读回光标下的深度值；带 TEXTURE_BINDING 用途，以便深度可视化
+pub(crate) fn create_depth_texture(
+    device: &Device,
+    size: [u32; 2],
+    format: TextureFormat,
+) -> Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: wgpu::Extent3d {
+            width: size[0].max(1),
+            height: size[1].max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::COPY_SRC
+            | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    })
+}
@@ -0,0 +1,20 @@
+// 深度可视化：全屏三角形逐像素读取深度缓冲区，近处为黑、远处为白
+
+@group(0) @binding(0) var r_depth: texture_depth_2d;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    // 深度纹理与颜色目标同尺寸，按像素坐标直接读取，无需采样器
+    let depth = textureLoad(r_depth, vec2<i32>(position.xy), 0);
+    let gray = vec3<f32>(depth);
+    if MANUAL_SRGB {
+        return vec4<f32>(srgb_from_linear(gray), 1.0);
+    }
+    return vec4<f32>(gray, 1.0);
+}
@@ -0,0 +1,69 @@
+// egui 网格的着色器
+// egui 输出的顶点坐标单位是逻辑点 (points)，颜色是 gamma 空间下预乘 alpha 的 sRGBA
+
+struct Locals {
+    screen_size: vec2<f32>, // 屏幕尺寸，单位为逻辑点
+    _padding: vec2<f32>,
+};
+
+@group(0) @binding(0) var<uniform> r_locals: Locals;
+
+@group(1) @binding(0) var r_tex_color: texture_2d<f32>;
+@group(1) @binding(1) var r_tex_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>, // Unorm8x4，gamma 空间
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+fn linear_from_gamma(srgb: vec3<f32>) -> vec3<f32> {
+    let cutoff = srgb < vec3<f32>(0.04045);
+    let lower = srgb / vec3<f32>(12.92);
+    let higher = pow((srgb + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
+    return select(higher, lower, cutoff);
+}
+
+fn gamma_from_linear(rgb: vec3<f32>) -> vec3<f32> {
+    let cutoff = rgb < vec3<f32>(0.0031308);
+    let lower = rgb * vec3<f32>(12.92);
+    let higher = vec3<f32>(1.055) * pow(rgb, vec3<f32>(1.0 / 2.4)) - vec3<f32>(0.055);
+    return select(higher, lower, cutoff);
+}
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    // 逻辑点 -> NDC，egui 的 y 轴朝下
+    out.clip_position = vec4<f32>(
+        2.0 * model.position.x / r_locals.screen_size.x - 1.0,
+        1.0 - 2.0 * model.position.y / r_locals.screen_size.y,
+        0.0,
+        1.0,
+    );
+    out.uv = model.uv;
+    out.color = model.color;
+    return out;
+}
+
+// 表面是 sRGB 格式时使用：在线性空间中混合，由硬件负责编码
+@fragment
+fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.uv);
+    let color_linear = vec4<f32>(linear_from_gamma(in.color.rgb), in.color.a);
+    return color_linear * tex_linear;
+}
+
+// 表面不是 sRGB 格式时使用：直接输出 gamma 空间的颜色
+@fragment
+fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.uv);
+    let tex_gamma = vec4<f32>(gamma_from_linear(tex_linear.rgb), tex_linear.a);
+    return in.color * tex_gamma;
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+
+use egui::{
+    ClippedPrimitive, TextureFilter, TextureId, TextureWrapMode, TexturesDelta, epaint::Primitive,
+};
+use wgpu::{
+    BindGroup, BindGroupLayout, Buffer, CommandEncoder, Device, LoadOp, Operations,
+    PipelineCompilationOptions, PipelineLayout, Queue, RenderPassColorAttachment,
+    RenderPassDescriptor, RenderPipeline, ShaderModule, Texture, TextureFormat, TextureView,
+    util::DeviceExt,
+};
+use winit::window::Window;
+
+/// 用户提供的 UI 闭包，每帧调用一次
+pub(crate) type UiCallback = Box<dyn FnMut(&egui::Context)>;
+
+// =================================================================================
+// App 端：持有 egui 上下文、winit 输入状态和用户 UI 闭包
+// =================================================================================
+pub(crate) struct EguiLayer {
+    ctx: egui::Context,
+    state: Option<egui_winit::State>,
+    ui: UiCallback,
+}
+
+impl EguiLayer {
+    pub(crate) fn new(ui: UiCallback) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            state: None,
+            ui,
+        }
+    }
+
+    // 窗口创建后才能初始化 winit 输入状态
+    pub(crate) fn init(&mut self, window: &Window, max_texture_side: usize) {
+        self.state = Some(egui_winit::State::new(
+            self.ctx.clone(),
+            egui::ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            None,
+            Some(max_texture_side),
+        ));
+    }
+
+    /// 把 winit 事件交给 egui，返回 egui 是否消费了该事件
+    pub(crate) fn on_window_event(
+        &mut self,
+        window: &Window,
+        event: &winit::event::WindowEvent,
+    ) -> bool {
+        let Some(state) = self.state.as_mut() else {
+            return false;
+        };
+        let response = state.on_window_event(window, event);
+        if response.repaint {
+            window.request_redraw();
+        }
+        response.consumed
+    }
+
+    /// 运行一帧用户 UI，产出交给 Renderer 绘制的数据
+    pub(crate) fn run(&mut self, window: &Window) -> Option<EguiFrame> {
+        let state = self.state.as_mut()?;
+        let raw_input = state.take_egui_input(window);
+        let mut output = self.ctx.run(raw_input, |ctx| (self.ui)(ctx));
+        state.handle_platform_output(window, output.platform_output);
+        // 执行 UI 发出的窗口命令，例如 ViewportCommand::Title；
+        // 截图和剪贴板动作请求不支持，直接丢弃
+        if let Some(viewport) = output.viewport_output.remove(&egui::ViewportId::ROOT) {
+            egui_winit::process_viewport_commands(
+                &self.ctx,
+                &mut egui::ViewportInfo::default(),
+                viewport.commands,
+                window,
+                &mut egui::ahash::HashSet::default(),
+            );
+        }
+
+        Some(EguiFrame {
+            primitives: self.ctx.tessellate(output.shapes, output.pixels_per_point),
+            textures_delta: output.textures_delta,
+            pixels_per_point: output.pixels_per_point,
+        })
+    }
+}
+
+/// 一帧 egui 的绘制数据
+pub(crate) struct EguiFrame {
+    primitives: Vec<ClippedPrimitive>,
+    textures_delta: TexturesDelta,
+    pixels_per_point: f32,
+}
+
+// =================================================================================
+// Renderer 端：用 wzui 的 device/queue 绘制 egui 网格
+// =================================================================================
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Locals {
+    screen_size: [f32; 2],
+    _padding: [f32; 2],
+}
+
+pub(crate) struct EguiPainter {
+    pipeline: RenderPipeline,
+    shader: ShaderModule,
+    pipeline_layout: PipelineLayout,
+    locals_buffer: Buffer,
+    locals_bind_group: BindGroup,
+    texture_layout: BindGroupLayout,
+    textures: HashMap<TextureId, (Texture, BindGroup)>,
+}
+
+impl EguiPainter {
+    pub(crate) fn new(device: &Device, format: TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Egui Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("egui.wgsl").into()),
+        });
+
+        let locals_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Egui Locals Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Egui Texture Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let locals_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Egui Locals Buffer"),
+            contents: bytemuck::bytes_of(&Locals {
+                screen_size: [1.0, 1.0],
+                _padding: [0.0; 2],
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let locals_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Egui Locals Bind Group"),
+            layout: &locals_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: locals_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Egui Pipeline Layout"),
+            bind_group_layouts: &[&locals_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = create_pipeline(device, &shader, &pipeline_layout, format);
+
+        Self {
+            pipeline,
+            shader,
+            pipeline_layout,
+            locals_buffer,
+            locals_bind_group,
+            texture_layout,
+            textures: HashMap::new(),
+        }
+    }
+
+    // 渲染目标格式变化时只重建管线，保留已上传的纹理：
+    // egui 只在首次使用时发送完整的字体图集，丢弃后 UI 将无法绘制
+    pub(crate) fn set_format(&mut self, device: &Device, format: TextureFormat) {
+        self.pipeline = create_pipeline(device, &self.shader, &self.pipeline_layout, format);
+    }
+
+    /// 在场景之后把 egui 绘制到 `view` 上（保留已有内容）
+    pub(crate) fn paint(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        size: [u32; 2],
+        frame: EguiFrame,
+    ) {
+        for (id, delta) in &frame.textures_delta.set {
+            self.update_texture(device, queue, *id, delta);
+        }
+
+        let ppp = frame.pixels_per_point;
+        queue.write_buffer(
+            &self.locals_buffer,
+            0,
+            bytemuck::bytes_of(&Locals {
+                screen_size: [size[0] as f32 / ppp, size[1] as f32 / ppp],
+                _padding: [0.0; 2],
+            }),
+        );
+
+        // 把所有网格拼接进同一对缓冲区，每个网格记录自己的索引区间和基准顶点
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut draws = Vec::new();
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in &frame.primitives
+        {
+            let Primitive::Mesh(mesh) = primitive else {
+                continue; // 不支持自定义绘制回调
+            };
+            if mesh.indices.is_empty() {
+                continue;
+            }
+            let first_index = indices.len() as u32;
+            let base_vertex = vertices.len() as i32;
+            vertices.extend_from_slice(&mesh.vertices);
+            indices.extend_from_slice(&mesh.indices);
+            draws.push((
+                *clip_rect,
+                mesh.texture_id,
+                first_index..indices.len() as u32,
+                base_vertex,
+            ));
+        }
+
+        if !draws.is_empty() {
+            let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Egui Vertex Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+            let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Egui Index Buffer"),
+                contents: bytemuck::cast_slice(&indices),
+                usage: wgpu::BufferUsages::INDEX,
+            });
+
+            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Egui Render Pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load, // 叠加在场景之上
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &self.locals_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+            render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+            for (clip_rect, texture_id, index_range, base_vertex) in draws {
+                let Some((_, bind_group)) = self.textures.get(&texture_id) else {
+                    continue;
+                };
+
+                // 裁剪矩形：逻辑点 -> 物理像素，并限制在目标范围内
+                let min_x = ((clip_rect.min.x * ppp).round() as u32).min(size[0]);
+                let min_y = ((clip_rect.min.y * ppp).round() as u32).min(size[1]);
+                let max_x = ((clip_rect.max.x * ppp).round() as u32).clamp(min_x, size[0]);
+                let max_y = ((clip_rect.max.y * ppp).round() as u32).clamp(min_y, size[1]);
+                if max_x == min_x || max_y == min_y {
+                    continue;
+                }
+
+                render_pass.set_scissor_rect(min_x, min_y, max_x - min_x, max_y - min_y);
+                render_pass.set_bind_group(1, bind_group, &[]);
+                render_pass.draw_indexed(index_range, base_vertex, 0..1);
+            }
+        }
+
+        for id in &frame.textures_delta.free {
+            self.textures.remove(id);
+        }
+    }
+
+    // UI 纹理占用的字节数，供 Renderer::memory_report 统计
+    pub(crate) fn texture_bytes(&self) -> u64 {
+        self.textures
+            .values()
+            .map(|(texture, _)| crate::memory::texture_bytes(texture))
+            .sum()
+    }
+
+    // 上传 egui 的纹理图集（整张新建或局部更新）
+    fn update_texture(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        id: TextureId,
+        delta: &egui::epaint::ImageDelta,
+    ) {
+        let egui::ImageData::Color(image) = &delta.image;
+        let [width, height] = image.size;
+        let size = wgpu::Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        };
+
+        // 局部更新：写入已有纹理的子区域
+        if let Some([x, y]) = delta.pos {
+            if let Some((texture, _)) = self.textures.get(&id) {
+                write_pixels(queue, texture, image, size, x as u32, y as u32);
+            }
+            return;
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Egui Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        write_pixels(queue, &texture, image, size, 0, 0);
+
+        let filter = |f: TextureFilter| match f {
+            TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+            TextureFilter::Linear => wgpu::FilterMode::Linear,
+        };
+        let address_mode = match delta.options.wrap_mode {
+            TextureWrapMode::ClampToEdge => wgpu::AddressMode::ClampToEdge,
+            TextureWrapMode::Repeat => wgpu::AddressMode::Repeat,
+            TextureWrapMode::MirroredRepeat => wgpu::AddressMode::MirrorRepeat,
+        };
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Egui Sampler"),
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            mag_filter: filter(delta.options.magnification),
+            min_filter: filter(delta.options.minification),
+            ..Default::default()
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Egui Texture Bind Group"),
+            layout: &self.texture_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        self.textures.insert(id, (texture, bind_group));
+    }
+}
+
+fn write_pixels(
+    queue: &Queue,
+    texture: &Texture,
+    image: &egui::ColorImage,
+    size: wgpu::Extent3d,
+    x: u32,
+    y: u32,
+) {
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d { x, y, z: 0 },
+            aspect: wgpu::TextureAspect::All,
+        },
+        bytemuck::cast_slice(&image.pixels),
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * size.width),
+            rows_per_image: Some(size.height),
+        },
+        size,
+    );
+}
+
+// 按目标颜色格式创建 egui 管线
+fn create_pipeline(
+    device: &Device,
+    shader: &ShaderModule,
+    pipeline_layout: &PipelineLayout,
+    format: TextureFormat,
+) -> RenderPipeline {
+    // 表面是否为 sRGB 决定了在哪个颜色空间里输出
+    let fs_entry = if format.is_srgb() {
+        "fs_main_linear_framebuffer"
+    } else {
+        "fs_main_gamma_framebuffer"
+    };
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Egui Pipeline"),
+        layout: Some(pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                // pos: [f32; 2], uv: [f32; 2], color: [u8; 4]
+                array_stride: std::mem::size_of::<egui::epaint::Vertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![
+                    0 => Float32x2,
+                    1 => Float32x2,
+                    2 => Unorm8x4,
+                ],
+            }],
+            compilation_options: PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(fs_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                // egui 输出的是预乘 alpha 的颜色
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None, // egui 不保证三角形的环绕方向
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
@@ -0,0 +1,49 @@
+use winit::event::KeyEvent;
+
+/// 快捷键的按键事件过滤策略。
+///
+/// 部分平台会在窗口获得焦点时发送合成的按键事件，按住按键还会产生重复事件；
+/// 直接处理这些事件会让开关类快捷键（线框、垂直同步、暂停等）被连续切换两次。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyFilter {
+    /// 只接受真实的按下：忽略合成事件和按键重复（默认）
+    #[default]
+    PressOnly,
+    /// 接受按键重复，但忽略合成事件，适合按住持续生效的操作
+    AllowRepeat,
+    /// 接受所有按下事件
+    All,
+}
+
+impl KeyFilter {
+    /// 判断一个按键事件是否应当触发快捷键。释放事件总是被忽略。
+    pub fn accepts(self, event: &KeyEvent, is_synthetic: bool) -> bool {
+        event.state.is_pressed() && self.accepts_press(event.repeat, is_synthetic)
+    }
+
+    // 按下事件的过滤规则；KeyEvent 无法在 winit 之外构造，拆出来便于测试
+    fn accepts_press(self, repeat: bool, is_synthetic: bool) -> bool {
+        match self {
+            Self::PressOnly => !is_synthetic && !repeat,
+            Self::AllowRepeat => !is_synthetic,
+            Self::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_repeat_and_synthetic_presses() {
+        // (重复, 合成) 的四种组合
+        let cases = [(false, false), (true, false), (false, true), (true, true)];
+        let accepted = |filter: KeyFilter| {
+            cases.map(|(repeat, is_synthetic)| filter.accepts_press(repeat, is_synthetic))
+        };
+        assert_eq!(accepted(KeyFilter::PressOnly), [true, false, false, false]);
+        assert_eq!(accepted(KeyFilter::AllowRepeat), [true, true, false, false]);
+        assert_eq!(accepted(KeyFilter::All), [true; 4]);
+    }
+}
@@ -0,0 +1,44 @@
+mod app;
+mod background;
+mod benchmark;
+mod blit;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod clock;
+mod color;
+mod context;
+mod depth;
+mod depth_debug;
+#[cfg(feature = "egui")]
+mod egui_layer;
+mod fullscreen;
+mod input;
+mod memory;
+mod mesh;
+mod pipeline;
+mod profile;
+mod readback;
+mod renderer;
+#[cfg(feature = "svg")]
+mod svg;
+mod vertex;
+mod vignette;
+
+pub use app::{App, AppConfig};
+pub use benchmark::{BenchmarkReport, FrameStats};
+#[cfg(feature = "clipboard")]
+pub use clipboard::ClipboardError;
+pub use color::ParseColorError;
+pub use context::DeviceContext;
+pub use input::KeyFilter;
+pub use memory::MemoryReport;
+pub use mesh::{MeshId, STRIP_RESTART_INDEX, deduplicate_vertices};
+pub use pipeline::{BindGroupHandle, PipelineHandle};
+pub use profile::ProfileScope;
+pub use renderer::{PresentToken, RenderTarget, Renderer, RendererConfig, RendererError};
+#[cfg(feature = "svg")]
+pub use svg::SvgError;
+pub use vertex::Vertex;
+
+#[cfg(feature = "egui")]
+pub use egui;
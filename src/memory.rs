@@ -0,0 +1,54 @@
+use std::fmt;
+
+use wgpu::Texture;
+
+/// Renderer 持有的 GPU 资源占用的内存估算（字节），由 `Renderer::memory_report` 返回。
+///
+/// 只统计 Renderer 自己创建或持有的资源；交换链纹理和用户自建的资源不计入。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// 顶点、索引和 uniform 缓冲区
+    pub buffer_bytes: u64,
+    /// 深度缓冲区、渲染目标和 UI 纹理
+    pub texture_bytes: u64,
+}
+
+impl MemoryReport {
+    pub fn total_bytes(&self) -> u64 {
+        self.buffer_bytes + self.texture_bytes
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        write!(
+            f,
+            "buffers: {:.2} MiB, textures: {:.2} MiB, total: {:.2} MiB",
+            mib(self.buffer_bytes),
+            mib(self.texture_bytes),
+            mib(self.total_bytes())
+        )
+    }
+}
+
+// 按格式、尺寸、mip 层级和采样数估算纹理大小；驱动实际分配时可能还有对齐和填充
+pub(crate) fn texture_bytes(texture: &Texture) -> u64 {
+    let format = texture.format();
+    // 组合深度/模板格式没有统一的块大小，按渲染目标的像素开销估算
+    let block_bytes = format
+        .block_copy_size(None)
+        .or_else(|| format.target_pixel_byte_cost())
+        .unwrap_or(4) as u64;
+    let (block_width, block_height) = format.block_dimensions();
+
+    (0..texture.mip_level_count())
+        .map(|level| {
+            let size = texture.size().mip_level_size(level, texture.dimension());
+            let blocks_x = size.width.div_ceil(block_width) as u64;
+            let blocks_y = size.height.div_ceil(block_height) as u64;
+            blocks_x * blocks_y * size.depth_or_array_layers as u64 * block_bytes
+        })
+        .sum::<u64>()
+        * texture.sample_count() as u64
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use wgpu::{BindGroup, BindGroupLayout, Buffer, Device, Queue, util::DeviceExt};
+
+use crate::{PipelineHandle, Vertex};
+
+/// 网格句柄，由 `Renderer::add_mesh` 等方法返回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MeshId(pub(crate) usize);
+
+// 网格：保留 CPU 端的顶点/索引副本，以便重新创建 GPU 缓冲区。
+// 顶点按字节保存，布局由所属着色器程序决定。
+// 无索引网格没有索引缓冲区，按顶点顺序直接绘制
+pub(crate) struct Mesh {
+    pub(crate) program: PipelineHandle,
+    pub(crate) vertex_data: Vec<u8>,
+    pub(crate) indices: Vec<u16>,
+    pub(crate) vertex_buffer: Buffer,
+    pub(crate) index_buffer: Option<Buffer>,
+    pub(crate) num_indices: u32,
+    pub(crate) num_vertices: u32,
+    // 内置程序的颜色调制 uniform 及其绑定组，未设置时使用 Renderer 的白色默认值
+    pub(crate) tint: Option<(Buffer, BindGroup)>,
+    // 按索引展开的无索引顶点缓冲区，供重心坐标线框使用，按需创建
+    pub(crate) expanded: Option<(Buffer, u32)>,
+}
+
+impl Mesh {
+    pub(crate) fn new(
+        device: &Device,
+        program: PipelineHandle,
+        vertex_data: &[u8],
+        indices: &[u16],
+    ) -> Self {
+        let vertex_buffer = create_vertex_buffer(device, vertex_data);
+        Self {
+            program,
+            vertex_data: vertex_data.to_vec(),
+            indices: indices.to_vec(),
+            vertex_buffer,
+            index_buffer: Some(create_index_buffer(device, indices)),
+            num_indices: indices.len() as u32,
+            num_vertices: 0,
+            tint: None,
+            expanded: None,
+        }
+    }
+
+    // 创建无索引网格，`stride` 为单个顶点的字节数
+    pub(crate) fn new_non_indexed(
+        device: &Device,
+        program: PipelineHandle,
+        vertex_data: &[u8],
+        stride: usize,
+    ) -> Self {
+        Self {
+            program,
+            vertex_data: vertex_data.to_vec(),
+            indices: Vec::new(),
+            vertex_buffer: create_vertex_buffer(device, vertex_data),
+            index_buffer: None,
+            num_indices: 0,
+            num_vertices: (vertex_data.len() / stride) as u32,
+            tint: None,
+            expanded: None,
+        }
+    }
+
+    // 用 CPU 端数据重新创建 GPU 缓冲区
+    pub(crate) fn reupload(&mut self, device: &Device) {
+        self.vertex_buffer = create_vertex_buffer(device, &self.vertex_data);
+        if self.index_buffer.is_some() {
+            self.index_buffer = Some(create_index_buffer(device, &self.indices));
+            self.num_indices = self.indices.len() as u32;
+        }
+        self.expanded = None;
+    }
+
+    // GPU 缓冲区占用的字节数
+    pub(crate) fn buffer_bytes(&self) -> u64 {
+        let expanded = self
+            .expanded
+            .as_ref()
+            .map_or(0, |(buffer, _)| buffer.size());
+        let index = self.index_buffer.as_ref().map_or(0, Buffer::size);
+        let tint = self.tint.as_ref().map_or(0, |(buffer, _)| buffer.size());
+        self.vertex_buffer.size() + index + expanded + tint
+    }
+
+    // 交换每个三角形的第 2、3 个索引（无索引网格交换第 2、3 个顶点），
+    // 反转环绕方向，并重新上传
+    pub(crate) fn flip_winding(&mut self, device: &Device) {
+        if self.index_buffer.is_some() {
+            for triangle in self.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        } else if self.num_vertices > 0 {
+            let stride = self.vertex_data.len() / self.num_vertices as usize;
+            for triangle in self.vertex_data.chunks_exact_mut(stride * 3) {
+                let (second, third) = triangle[stride..].split_at_mut(stride);
+                second.swap_with_slice(third);
+            }
+        }
+        self.reupload(device);
+    }
+
+    // 按裁剪空间 xy 的有向面积统计，背向三角形多于正向三角形时返回 true。
+    // 只适用于使用内置 Vertex 布局的三角形列表网格
+    pub(crate) fn mostly_back_facing(&self) -> bool {
+        let stride = size_of::<Vertex>();
+        let position = |i: usize| {
+            self.vertex_data
+                .get(i * stride..(i + 1) * stride)
+                .map(|bytes| bytemuck::pod_read_unaligned::<Vertex>(bytes).position)
+        };
+        let corners: Vec<usize> = if self.index_buffer.is_some() {
+            self.indices.iter().map(|&i| i as usize).collect()
+        } else {
+            (0..self.num_vertices as usize).collect()
+        };
+        let (mut front, mut back) = (0usize, 0usize);
+        for triangle in corners.chunks_exact(3) {
+            let (Some(a), Some(b), Some(c)) = (
+                position(triangle[0]),
+                position(triangle[1]),
+                position(triangle[2]),
+            ) else {
+                continue;
+            };
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+            if area > 0.0 {
+                front += 1;
+            } else if area < 0.0 {
+                back += 1;
+            }
+        }
+        back > front
+    }
+
+    // 设置颜色调制，已有 uniform 时直接改写，不重新创建
+    pub(crate) fn set_tint(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        layout: &BindGroupLayout,
+        tint: Option<[f32; 3]>,
+    ) {
+        match (tint, &self.tint) {
+            (None, _) => self.tint = None,
+            (Some(tint), Some((buffer, _))) => {
+                queue.write_buffer(buffer, 0, bytemuck::cast_slice(&tint_uniform(tint)));
+            }
+            (Some(tint), None) => self.tint = Some(create_tint(device, layout, tint)),
+        }
+    }
+
+    // 创建按索引展开的顶点缓冲区：每个三角形独占三个顶点，
+    // 着色器据此用 vertex_index % 3 得到重心坐标。
+    // 无索引网格本身就是这种排列，不需要展开。
+    // 引用了不存在的顶点（例如条带用的 STRIP_RESTART_INDEX）的三角形整个跳过，
+    // 末尾不足三个的索引也忽略
+    pub(crate) fn ensure_expanded(&mut self, device: &Device, stride: usize) {
+        if self.expanded.is_some() || self.index_buffer.is_none() {
+            return;
+        }
+        let vertex_count = self.vertex_data.len() / stride;
+        let mut data = Vec::with_capacity(self.indices.len() * stride);
+        for triangle in self.indices.chunks_exact(3) {
+            if triangle.iter().any(|&index| index as usize >= vertex_count) {
+                continue;
+            }
+            for &index in triangle {
+                let start = index as usize * stride;
+                data.extend_from_slice(&self.vertex_data[start..start + stride]);
+            }
+        }
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Expanded Vertex Buffer"),
+            contents: &data,
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        self.expanded = Some((buffer, (data.len() / stride) as u32));
+    }
+}
+
+// 内置着色器 @group(0) 的布局：顶点阶段读取的 vec4 颜色调制
+pub(crate) fn create_tint_layout(device: &Device) -> BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Tint Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    })
+}
+
+// 创建颜色调制 uniform 及其绑定组
+pub(crate) fn create_tint(
+    device: &Device,
+    layout: &BindGroupLayout,
+    tint: [f32; 3],
+) -> (Buffer, BindGroup) {
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Tint Buffer"),
+        contents: bytemuck::cast_slice(&tint_uniform(tint)),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Tint Bind Group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+    (buffer, bind_group)
+}
+
+// uniform 按 vec4 对齐，第四个分量不使用
+fn tint_uniform([r, g, b]: [f32; 3]) -> [f32; 4] {
+    [r, g, b, 1.0]
+}
+
+// 创建顶点缓冲区
+fn create_vertex_buffer(device: &Device, vertex_data: &[u8]) -> Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Vertex Buffer"),
+        contents: vertex_data,
+        usage: wgpu::BufferUsages::VERTEX,
+    })
+}
+
+// 创建索引缓冲区
+fn create_index_buffer(device: &Device, indices: &[u16]) -> Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Index Buffer"),
+        contents: bytemuck::cast_slice(indices),
+        usage: wgpu::BufferUsages::INDEX,
+    })
+}
+
+/// 图元重启索引：使用带状拓扑时，索引缓冲区中的该值会结束当前条带，
+/// 下一个索引开始新的条带，从而在一个网格中放多条互不相连的条带
+pub const STRIP_RESTART_INDEX: u16 = u16::MAX;
+
+// 判断三角形列表是否全部背向屏幕：顶点坐标直接作为裁剪空间坐标，
+// 屏幕上的有向面积为负即为顺时针，在默认的 CCW 正面设置下会被剔除。
+// 退化三角形不计入；没有有效三角形时返回 false
+pub(crate) fn all_back_facing(vertices: &[Vertex], indices: &[u16]) -> bool {
+    let mut any = false;
+    for triangle in indices.chunks_exact(3) {
+        let position = |i: usize| vertices.get(triangle[i] as usize).map(|v| v.position);
+        // 索引越界的网格交给 GPU 校验报错，这里不做判断
+        let (Some(a), Some(b), Some(c)) = (position(0), position(1), position(2)) else {
+            return false;
+        };
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        if area > 0.0 {
+            return false;
+        }
+        any |= area < 0.0;
+    }
+    any
+}
+
+// 顶点去重时的量化步长：分量四舍五入到该值的整数倍后相等即视为相同
+const DEDUP_EPSILON: f32 = 1e-5;
+
+/// 对无索引的三角形汤去重，返回去重后的顶点和对应的索引。
+///
+/// 浮点数不能直接哈希（`-0.0`/`0.0`、微小误差），因此先把位置和颜色的每个分量
+/// 四舍五入到 `1e-5` 的整数倍，再以量化后的整数作为键。
+///
+/// 量化是按格子划分的：落在同一格子里的分量合并，而跨过格子边界的两个值即使
+/// 相差远小于 `1e-5` 也不会合并（例如 `0.5e-5 - δ` 和 `0.5e-5 + δ`）。
+///
+/// # Panics
+///
+/// 去重后顶点数超过 `u16` 索引范围时 panic。
+pub fn deduplicate_vertices(vertices: &[Vertex]) -> (Vec<Vertex>, Vec<u16>) {
+    let quantize = |v: f32| (v / DEDUP_EPSILON).round() as i64;
+
+    let mut lookup: HashMap<[i64; 6], u16> = HashMap::new();
+    let mut unique = Vec::new();
+    let mut indices = Vec::with_capacity(vertices.len());
+
+    for vertex in vertices {
+        let [x, y, z] = vertex.position;
+        let [r, g, b] = vertex.color;
+        let key = [x, y, z, r, g, b].map(quantize);
+
+        let index = *lookup.entry(key).or_insert_with(|| {
+            let index =
+                u16::try_from(unique.len()).expect("too many unique vertices for u16 indices");
+            unique.push(*vertex);
+            index
+        });
+        indices.push(index);
+    }
+
+    (unique, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deduplicate_quad() {
+        let vertex = |x, y| Vertex {
+            position: [x, y, 0.0],
+            color: [1.0, 1.0, 1.0],
+        };
+        let [a, b, c, d] = [
+            vertex(0.0, 0.0),
+            vertex(1.0, 0.0),
+            vertex(1.0, 1.0),
+            vertex(0.0, 1.0),
+        ];
+        // 两个三角形共用对角线 a-c，其中一个 a 带有量化步长以下的误差
+        let mut a2 = a;
+        a2.position[0] = 1e-7;
+        let (unique, indices) = deduplicate_vertices(&[a, b, c, a2, c, d]);
+        assert_eq!(unique.len(), 4);
+        assert_eq!(indices, [0, 1, 2, 0, 2, 3]);
+        assert_eq!(unique[3].position, d.position);
+    }
+}
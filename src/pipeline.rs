@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use wgpu::{
+    BindGroup, CompareFunction, Device, IndexFormat, PipelineCompilationOptions, PipelineLayout,
+    PrimitiveTopology, RenderPipeline, ShaderModule, TextureFormat, VertexBufferLayout,
+};
+
+use crate::depth::DEFAULT_DEPTH_FORMAT;
+
+/// 着色器程序句柄，由 `Renderer::register_pipeline` 返回。
+///
+/// `PipelineHandle::default()` 是内置的 `position + color` 程序。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PipelineHandle(pub(crate) usize);
+
+/// 绑定组句柄，由 `Renderer::create_bind_group` 返回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BindGroupHandle(pub(crate) usize);
+
+// 着色器程序：WGSL 模块、管线布局、顶点布局及用户设置的 override 常量。
+// 入口固定为 `vs_main`/`fs_main`；内置程序另有 `fs_backface_debug`、
+// `fs_face_debug` 和线框入口
+#[derive(Clone)]
+pub(crate) struct Program {
+    pub(crate) label: String,
+    pub(crate) shader: ShaderModule,
+    pub(crate) layout: PipelineLayout,
+    pub(crate) vertex_layout: VertexBufferLayout<'static>,
+    pub(crate) constants: BTreeMap<String, f64>,
+    // 按组索引排列，长度等于管线布局中的绑定组布局数，绘制前必须全部设置
+    pub(crate) bind_groups: Vec<Option<BindGroup>>,
+    // WGSL 源文件，设置后 reload_all_pipelines 会从这里重新编译
+    pub(crate) source_path: Option<PathBuf>,
+}
+
+// 管线缓存的键：所有会改变管线状态的选项都放在这里，
+// 相同的键复用同一条管线，切换选项时无需重复编译
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct PipelineKey {
+    pub(crate) program: PipelineHandle,
+    pub(crate) topology: PrimitiveTopology,
+    pub(crate) depth_format: TextureFormat,
+    pub(crate) depth_compare: CompareFunction,
+    pub(crate) backface_debug: bool,
+    // 正面绿色、背面红色；优先于背面调试
+    pub(crate) face_debug: bool,
+    pub(crate) manual_srgb: bool,
+    // 开启深度预渲染时，颜色遍使用 `Equal` 比较且不写深度
+    pub(crate) depth_prepass: bool,
+    // 深度预渲染遍本身：只有顶点着色器，不输出颜色
+    pub(crate) depth_only: bool,
+    // 重心坐标线框，只有内置程序支持
+    pub(crate) wireframe: Option<Wireframe>,
+}
+
+impl PipelineKey {
+    // 某个着色器程序实际使用的键：自定义程序没有调试和线框入口，照常用
+    // `fs_main` 绘制；线框又依赖按三角形列表展开顶点，其他拓扑下也忽略线框设置
+    pub(crate) fn for_program(self, program: PipelineHandle) -> Self {
+        let builtin = program == PipelineHandle::default();
+        let wireframe = self
+            .wireframe
+            .filter(|_| builtin && self.topology == PrimitiveTopology::TriangleList);
+        Self {
+            program,
+            backface_debug: self.backface_debug && builtin,
+            face_debug: self.face_debug && builtin,
+            wireframe,
+            ..self
+        }
+    }
+}
+
+// 线框参数以位模式保存，使管线键可以哈希
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Wireframe {
+    thickness: u32,
+    color: [u32; 3],
+}
+
+impl Wireframe {
+    pub(crate) fn new(thickness: f32, color: [f32; 3]) -> Self {
+        Self {
+            thickness: thickness.to_bits(),
+            color: color.map(f32::to_bits),
+        }
+    }
+
+    fn constants(self) -> [(&'static str, f64); 4] {
+        let [r, g, b] = self.color.map(|c| f32::from_bits(c) as f64);
+        [
+            ("WIREFRAME_THICKNESS", f32::from_bits(self.thickness) as f64),
+            ("WIREFRAME_R", r),
+            ("WIREFRAME_G", g),
+            ("WIREFRAME_B", b),
+        ]
+    }
+}
+
+impl Default for PipelineKey {
+    fn default() -> Self {
+        Self {
+            program: PipelineHandle::default(),
+            topology: PrimitiveTopology::TriangleList,
+            depth_format: DEFAULT_DEPTH_FORMAT,
+            depth_compare: CompareFunction::Less,
+            backface_debug: false,
+            face_debug: false,
+            manual_srgb: false,
+            depth_prepass: false,
+            depth_only: false,
+            wireframe: None,
+        }
+    }
+}
+
+// 带状拓扑必须指定索引格式，索引值 0xFFFF 会断开条带（图元重启）
+pub(crate) fn strip_index_format(topology: PrimitiveTopology) -> Option<IndexFormat> {
+    topology.is_strip().then_some(IndexFormat::Uint16)
+}
+
+// 按目标颜色格式和管线键创建渲染管线；渲染目标的格式变化时需要重建
+pub(crate) fn create_render_pipeline(
+    device: &Device,
+    program: &Program,
+    format: TextureFormat,
+    key: PipelineKey,
+) -> RenderPipeline {
+    let shader = &program.shader;
+    // 背面调试和线框都需要关闭剔除，才能看到背面
+    let (vs_entry, fs_entry, cull_mode) = if key.wireframe.is_some() {
+        ("vs_wireframe", "fs_wireframe", None)
+    } else if key.face_debug {
+        ("vs_main", "fs_face_debug", None)
+    } else if key.backface_debug {
+        ("vs_main", "fs_backface_debug", None)
+    } else {
+        ("vs_main", "fs_main", Some(wgpu::Face::Back))
+    };
+
+    // 对应着色器中的 override 常量，只在需要时传入
+    let mut constants: Vec<(&str, f64)> = Vec::new();
+    if key.manual_srgb {
+        constants.push(("MANUAL_SRGB", 1.0));
+    }
+    if let Some(wireframe) = key.wireframe {
+        constants.extend(wireframe.constants());
+    }
+    constants.extend(
+        program
+            .constants
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value)),
+    );
+
+    // 预渲染已经建立了深度，颜色遍只绘制深度恰好相等的片元
+    let (depth_compare, depth_write_enabled) = if key.depth_prepass && !key.depth_only {
+        (CompareFunction::Equal, false)
+    } else {
+        (key.depth_compare, true)
+    };
+
+    let targets = [Some(wgpu::ColorTargetState {
+        format,
+        blend: Some(wgpu::BlendState::REPLACE),
+        write_mask: wgpu::ColorWrites::ALL,
+    })];
+    let fragment = (!key.depth_only).then(|| wgpu::FragmentState {
+        module: shader,
+        entry_point: Some(fs_entry), // 片元着色器入口函数
+        targets: &targets,
+        compilation_options: PipelineCompilationOptions {
+            constants: &constants,
+            ..Default::default()
+        },
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(&program.label),
+        layout: Some(&program.layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some(vs_entry), // 顶点着色器入口函数
+            buffers: std::slice::from_ref(&program.vertex_layout), // 顶点布局描述
+            compilation_options: PipelineCompilationOptions {
+                constants: &constants,
+                ..Default::default()
+            },
+        },
+        fragment,
+        primitive: wgpu::PrimitiveState {
+            topology: key.topology,
+            strip_index_format: strip_index_format(key.topology),
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: key.depth_format,
+            depth_write_enabled,
+            depth_compare,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+type Timings = Arc<Mutex<HashMap<String, f32>>>;
+
+/// CPU 计时范围，由 `Renderer::scope` 创建，离开作用域时把经过的时间
+/// 累加到本帧的统计中。
+///
+/// 不借用 Renderer，持有期间可以继续调用 Renderer 的方法；范围可以嵌套，
+/// 同名范围在一帧内多次出现时累加。
+#[must_use = "the scope measures until it is dropped"]
+pub struct ProfileScope {
+    name: String,
+    start: Instant,
+    timings: Timings,
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let ms = self.start.elapsed().as_secs_f32() * 1000.0;
+        let mut timings = self.timings.lock().unwrap();
+        *timings.entry(std::mem::take(&mut self.name)).or_default() += ms;
+    }
+}
+
+// 收集当前帧的计时，每次 render() 开始时把它转存为上一帧的结果
+pub(crate) struct CpuProfiler {
+    current: Timings,
+    last_frame: HashMap<String, f32>,
+}
+
+impl CpuProfiler {
+    pub(crate) fn new() -> Self {
+        Self {
+            current: Timings::default(),
+            last_frame: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn scope(&self, name: &str) -> ProfileScope {
+        ProfileScope {
+            name: name.to_owned(),
+            start: Instant::now(),
+            timings: self.current.clone(),
+        }
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.last_frame = std::mem::take(&mut *self.current.lock().unwrap());
+    }
+
+    pub(crate) fn last_frame(&self) -> &HashMap<String, f32> {
+        &self.last_frame
+    }
+}
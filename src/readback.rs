@@ -0,0 +1,110 @@
+use std::iter::once;
+
+use wgpu::{Device, Extent3d, Queue, TexelCopyTextureInfo, Texture};
+
+// 把整个纹理读回 CPU，返回逐行紧密排列的字节。
+//
+// 纹理必须带有 `COPY_SRC` 用途，格式必须是非压缩的单平面颜色格式。
+#[cfg(any(feature = "clipboard", test))]
+pub(crate) fn read_texture(device: &Device, queue: &Queue, texture: &Texture) -> Vec<u8> {
+    let bytes_per_pixel = texture
+        .format()
+        .block_copy_size(None)
+        .expect("texture format cannot be copied to a buffer");
+    read_region(
+        device,
+        queue,
+        texture.as_image_copy(),
+        texture.size(),
+        bytes_per_pixel,
+    )
+}
+
+// 读回深度纹理中的单个像素，返回归一化的深度值。
+// Depth24Plus 等格式的深度无法拷贝到缓冲区，返回 None
+pub(crate) fn read_depth_texel(
+    device: &Device,
+    queue: &Queue,
+    texture: &Texture,
+    [x, y]: [u32; 2],
+) -> Option<f32> {
+    let aspect = wgpu::TextureAspect::DepthOnly;
+    let bytes_per_pixel = texture.format().block_copy_size(Some(aspect))?;
+    let bytes = read_region(
+        device,
+        queue,
+        TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d { x, y, z: 0 },
+            aspect,
+        },
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        bytes_per_pixel,
+    );
+    match texture.format() {
+        wgpu::TextureFormat::Depth16Unorm => {
+            Some(u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32)
+        }
+        _ => Some(f32::from_le_bytes(bytes[..4].try_into().unwrap())),
+    }
+}
+
+// 把纹理的一块区域拷贝到缓冲区并读回，去掉行对齐填充。
+// 会阻塞等待 GPU 完成拷贝。
+fn read_region(
+    device: &Device,
+    queue: &Queue,
+    source: TexelCopyTextureInfo,
+    extent: Extent3d,
+    bytes_per_pixel: u32,
+) -> Vec<u8> {
+    let unpadded_bytes_per_row = extent.width * bytes_per_pixel;
+    // 缓冲区中每行的字节数必须按 COPY_BYTES_PER_ROW_ALIGNMENT (256) 对齐
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: (padded_bytes_per_row * extent.height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        source,
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(extent.height),
+            },
+        },
+        extent,
+    );
+    queue.submit(once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    // 阻塞直到拷贝完成；映射回调在轮询中触发，返回时缓冲区已经可读
+    device
+        .poll(wgpu::PollType::Wait)
+        .expect("failed to wait for texture readback");
+
+    let mapped = slice.get_mapped_range();
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * extent.height) as usize);
+    for row in mapped.chunks(padded_bytes_per_row as usize) {
+        pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+    }
+    drop(mapped);
+    buffer.unmap();
+    pixels
+}
@@ -0,0 +1,2152 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    iter::once,
+    sync::{Arc, mpsc},
+    time::{Duration, Instant},
+};
+
+use wgpu::{
+    Backend, BindGroup, BindGroupEntry, BindGroupLayout, Color, CommandEncoderDescriptor,
+    CompareFunction, InstanceFlags, LoadOp, Operations, PresentMode, PrimitiveTopology, RenderPass,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, Surface, SurfaceConfiguration,
+    SurfaceError, SurfaceTexture, Texture, TextureFormat, TextureUsages, TextureView,
+    TextureViewDescriptor, VertexBufferLayout,
+};
+use winit::{
+    dpi::{LogicalSize, PhysicalSize},
+    window::{CursorGrabMode, Window},
+};
+
+use crate::background::BackgroundPainter;
+use crate::benchmark::{BenchmarkReport, FrameStats};
+use crate::blit::{BlitDraw, BlitPainter};
+#[cfg(feature = "clipboard")]
+use crate::clipboard::{self, ClipboardError};
+use crate::clock::AnimationClock;
+use crate::color::{ParseColorError, encode_srgb, hsv_to_rgb, parse_hex_color};
+use crate::context::DeviceContext;
+use crate::depth::{DEFAULT_DEPTH_FORMAT, create_depth_texture, validate_depth_format};
+use crate::depth_debug::DepthDebugPainter;
+#[cfg(feature = "egui")]
+use crate::egui_layer::{EguiFrame, EguiPainter};
+use crate::memory::{self, MemoryReport};
+use crate::mesh::{
+    Mesh, MeshId, all_back_facing, create_tint, create_tint_layout, deduplicate_vertices,
+};
+use crate::pipeline::{
+    BindGroupHandle, PipelineHandle, PipelineKey, Program, Wireframe, create_render_pipeline,
+};
+use crate::profile::{CpuProfiler, ProfileScope};
+use crate::readback::read_depth_texel;
+#[cfg(feature = "clipboard")]
+use crate::readback::read_texture;
+#[cfg(feature = "svg")]
+use crate::svg::{self, SvgError};
+use crate::vertex::{INDICES, VERTICES, Vertex};
+use crate::vignette::VignettePainter;
+
+// 默认的清屏颜色：深蓝色
+const DEFAULT_CLEAR_COLOR: Color = Color {
+    r: 0.1,
+    g: 0.2,
+    b: 0.3,
+    a: 1.0,
+};
+
+/// 创建 Renderer 失败的原因，由 `Renderer::new_with_timeout` 返回
+#[derive(Debug)]
+pub enum RendererError {
+    /// 没有可用的图形适配器
+    NoAdapter(wgpu::RequestAdapterError),
+    /// 适配器拒绝创建设备
+    RequestDevice(wgpu::RequestDeviceError),
+    /// GPU 初始化没有在给定时间内完成，通常是驱动配置有问题
+    Timeout(Duration),
+}
+
+impl fmt::Display for RendererError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoAdapter(e) => write!(f, "no suitable graphics adapter: {e}"),
+            Self::RequestDevice(e) => write!(f, "failed to create graphics device: {e}"),
+            Self::Timeout(timeout) => {
+                write!(f, "GPU initialization did not finish within {timeout:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RendererError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NoAdapter(e) => Some(e),
+            Self::RequestDevice(e) => Some(e),
+            Self::Timeout(_) => None,
+        }
+    }
+}
+
+impl From<wgpu::RequestAdapterError> for RendererError {
+    fn from(e: wgpu::RequestAdapterError) -> Self {
+        Self::NoAdapter(e)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for RendererError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        Self::RequestDevice(e)
+    }
+}
+
+/// 窗口尺寸变化回调
+type ResizeCallback = Box<dyn FnMut(PhysicalSize<u32>, TextureFormat)>;
+
+/// 创建 Renderer 时的配置
+#[derive(Debug, Clone, Default)]
+pub struct RendererConfig {
+    /// 是否在片元着色器中手动做 sRGB 编码，而不依赖 sRGB 表面。
+    ///
+    /// GL/WebGL 后端对 sRGB 表面的处理与原生后端不同，颜色会发白；
+    /// 开启后改用非 sRGB 表面格式并在着色器中编码。`None` 表示自动检测：
+    /// GL 后端开启，表面不提供任何 sRGB 格式时也开启。
+    /// 可用 `Renderer::manual_srgb_active` 查询实际结果。
+    pub manual_srgb: Option<bool>,
+    /// 深度缓冲区格式，例如 `Depth16Unorm` 可以在移动端节省带宽。
+    /// `None` 为 `Depth32Float`；设备不支持所选格式时同样退回 `Depth32Float`。
+    pub depth_format: Option<TextureFormat>,
+    /// wgpu 实例标志，例如 `InstanceFlags::VALIDATION | InstanceFlags::DEBUG` 开启校验层。
+    /// `None` 时调试构建开启、发布构建关闭，并读取 `WGPU_VALIDATION` 等环境变量。
+    /// 只在 Renderer 自己创建设备时生效，使用共享的 `DeviceContext` 时被忽略。
+    pub instance_flags: Option<InstanceFlags>,
+}
+
+/// 渲染输出的位置
+#[derive(Debug, Clone)]
+pub enum RenderTarget {
+    /// 窗口表面（默认），每帧获取并呈现交换链纹理
+    Surface,
+    /// 用户提供的纹理，必须带有 `RENDER_ATTACHMENT` 用途；不会获取或呈现表面
+    Texture(Texture),
+}
+
+/// 已绘制并提交、但尚未呈现的一帧，由 `Renderer::take_present_token` 取得，
+/// 交给 `Renderer::present` 呈现。
+///
+/// 持有期间交换链纹理保持被占用；直接丢弃会放弃这一帧，不会显示。
+#[must_use = "dropping a PresentToken discards the frame"]
+pub struct PresentToken(SurfaceTexture);
+
+// =================================================================================
+// 步骤 1.2: 扩展 Renderer 来持有渲染所需资源
+// =================================================================================
+pub struct Renderer {
+    window: Arc<Window>,
+    surface: Surface<'static>,
+    config: SurfaceConfiguration,
+    size: PhysicalSize<u32>,
+    context: DeviceContext,
+    programs: Vec<Program>,
+    bind_groups: Vec<BindGroup>,
+    pipelines: HashMap<PipelineKey, RenderPipeline>,
+    pipeline_key: PipelineKey,
+    backend: Backend,
+    manual_srgb: bool,
+    target: RenderTarget,
+    depth_texture: Texture,
+    on_resize: Option<ResizeCallback>,
+    cursor_grabbed: bool,
+    mouse_delta: (f64, f64),
+    meshes: Vec<Mesh>,
+    clear_on_present_only: bool,
+    clear_color: Color,
+    // 背景色相循环的周期（秒），`None` 表示使用固定的清屏颜色
+    animated_background: Option<f32>,
+    // 顶部、底部颜色；设置后先绘制全屏渐变，再绘制场景
+    background_gradient: Option<[Color; 2]>,
+    background_painter: Option<BackgroundPainter>,
+    // 暗角的 [强度, 半径]，None 表示关闭
+    vignette: Option<[f32; 2]>,
+    vignette_painter: Option<VignettePainter>,
+    // 固定的内容宽高比，窗口比例不同时在两侧或上下留出黑边
+    target_aspect: Option<f32>,
+    letterbox_color: Color,
+    // 本帧排队的纹理绘制，render() 结束后清空
+    blit_draws: Vec<BlitDraw>,
+    blit_painter: Option<BlitPainter>,
+    // 开启时在场景之后用深度灰度图覆盖画面
+    depth_debug: bool,
+    depth_debug_painter: Option<DepthDebugPainter>,
+    // 内置程序 @group(0) 的布局，以及未设置颜色调制的网格使用的白色绑定组
+    tint_layout: BindGroupLayout,
+    default_tint: BindGroup,
+    clock: AnimationClock,
+    profiler: CpuProfiler,
+    wait_for_first_frame: bool,
+    present_on_render: bool,
+    pending_present: Option<SurfaceTexture>,
+    #[cfg(feature = "egui")]
+    egui_painter: Option<EguiPainter>,
+    #[cfg(feature = "egui")]
+    egui_frame: Option<EguiFrame>,
+    // 按 (路径, 尺寸) 缓存的 SVG 栅格化结果
+    #[cfg(feature = "svg")]
+    svg_textures: HashMap<(std::path::PathBuf, u32), Texture>,
+}
+
+impl Renderer {
+    pub async fn new(window: Arc<Window>) -> Self {
+        Self::with_config(window, RendererConfig::default()).await
+    }
+
+    pub async fn with_config(window: Arc<Window>, renderer_config: RendererConfig) -> Self {
+        let context = match renderer_config.instance_flags {
+            Some(flags) => DeviceContext::with_instance_flags(flags).await,
+            None => DeviceContext::new().await,
+        };
+        Self::with_context_and_config(&context, window, renderer_config)
+    }
+
+    /// 同步版本的 `new`：阻塞当前线程直到 GPU 初始化完成；请求适配器和设备
+    /// 超过 `timeout` 时返回 `RendererError::Timeout`，而不是在驱动配置有问题
+    /// 的系统上无声地卡住。
+    ///
+    /// 原生后端的适配器和设备请求会在驱动内部阻塞当前线程，单纯让 future 与
+    /// 计时器竞争无法打断它，因此这两步放在辅助线程中进行，本方法最多等待
+    /// `timeout`。超时后辅助线程被分离，仍可能一直卡在驱动中。之后的表面和
+    /// 管线创建在调用线程上进行，不计入超时。
+    pub fn new_with_timeout(window: Arc<Window>, timeout: Duration) -> Result<Self, RendererError> {
+        Self::with_config_and_timeout(window, RendererConfig::default(), timeout)
+    }
+
+    /// 同 `new_with_timeout`，但使用给定的配置；`instance_flags`、`depth_format`、
+    /// `manual_srgb` 等与 `with_config` 的含义相同
+    pub fn with_config_and_timeout(
+        window: Arc<Window>,
+        renderer_config: RendererConfig,
+        timeout: Duration,
+    ) -> Result<Self, RendererError> {
+        let flags = renderer_config
+            .instance_flags
+            .unwrap_or_else(|| InstanceFlags::default().with_env());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(pollster::block_on(DeviceContext::try_with_instance_flags(
+                flags,
+            )));
+        });
+        // 辅助线程 panic 时通道断开，同样视为初始化没有完成
+        let context = receiver
+            .recv_timeout(timeout)
+            .map_err(|_| RendererError::Timeout(timeout))??;
+        Ok(Self::with_context_and_config(
+            &context,
+            window,
+            renderer_config,
+        ))
+    }
+
+    /// 使用已有的设备上下文为窗口创建 Renderer，多个窗口共享同一个设备。
+    /// 可以用 `DeviceContext::new` 创建，或用 `Renderer::device_context` 从已有的 Renderer 取得。
+    pub fn new_with_context(context: &DeviceContext, window: Arc<Window>) -> Self {
+        Self::with_context_and_config(context, window, RendererConfig::default())
+    }
+
+    pub fn with_context_and_config(
+        context: &DeviceContext,
+        window: Arc<Window>,
+        renderer_config: RendererConfig,
+    ) -> Self {
+        let context = context.clone();
+        let DeviceContext {
+            instance,
+            adapter,
+            device,
+            ..
+        } = &context;
+        let size = window.inner_size();
+        let surface = instance.create_surface(window.clone()).unwrap();
+
+        let backend = adapter.get_info().backend;
+        let mut manual_srgb = renderer_config
+            .manual_srgb
+            .unwrap_or(backend == Backend::Gl);
+
+        // 手动编码时需要非 sRGB 表面，否则会被编码两次
+        let surface_caps = surface.get_capabilities(adapter);
+        let format = surface_caps
+            .formats
+            .iter()
+            .copied()
+            .find(|f| f.is_srgb() != manual_srgb)
+            .unwrap_or(surface_caps.formats[0]);
+        // 没有 sRGB 表面格式可选时，自动检测模式下改为在着色器中编码，
+        // 否则线性颜色直接写入非 sRGB 表面会显得偏暗
+        if !manual_srgb && !format.is_srgb() && renderer_config.manual_srgb.is_none() {
+            eprintln!(
+                "No sRGB surface format available, using {format:?} with manual sRGB encoding"
+            );
+            manual_srgb = true;
+        }
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo, // VSync
+            desired_maximum_frame_latency: 2,
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+        };
+
+        surface.configure(device, &config);
+
+        // =================================================================================
+        // 步骤 1.3: 创建着色器、管线和缓冲区
+        // =================================================================================
+
+        // 内置程序的 @group(0) 为逐网格的颜色调制
+        let tint_layout = create_tint_layout(device);
+        let (_, default_tint) = create_tint(device, &tint_layout, [1.0; 3]);
+        let programs = vec![builtin_program(device, &tint_layout)];
+
+        let depth_format = renderer_config
+            .depth_format
+            .map_or(DEFAULT_DEPTH_FORMAT, |format| {
+                validate_depth_format(adapter, device, format)
+            });
+
+        // 创建渲染管线，按管线键缓存
+        let pipeline_key = PipelineKey {
+            manual_srgb: manual_srgb && !config.format.is_srgb(),
+            depth_format,
+            ..Default::default()
+        };
+        let render_pipeline =
+            create_render_pipeline(device, &programs[0], config.format, pipeline_key);
+        let pipelines = HashMap::from([(pipeline_key, render_pipeline)]);
+
+        // 创建深度缓冲区
+        let depth_texture =
+            create_depth_texture(device, [config.width, config.height], depth_format);
+
+        // 默认的正方形作为第一个网格
+        let meshes = vec![default_mesh(device)];
+
+        Self {
+            window,
+            surface,
+            config,
+            size,
+            context,
+            programs,
+            bind_groups: Vec::new(),
+            pipelines, // <-- 保存管线
+            pipeline_key,
+            backend,
+            manual_srgb,
+            target: RenderTarget::Surface,
+            depth_texture,
+            on_resize: None,
+            cursor_grabbed: false,
+            mouse_delta: (0.0, 0.0),
+            meshes, // <-- 保存网格（顶点/索引缓冲区）
+            clear_on_present_only: false,
+            clear_color: DEFAULT_CLEAR_COLOR,
+            animated_background: None,
+            background_gradient: None,
+            background_painter: None,
+            vignette: None,
+            vignette_painter: None,
+            target_aspect: None,
+            letterbox_color: Color::BLACK,
+            blit_draws: Vec::new(),
+            blit_painter: None,
+            depth_debug: false,
+            depth_debug_painter: None,
+            tint_layout,
+            default_tint,
+            clock: AnimationClock::new(),
+            profiler: CpuProfiler::new(),
+            wait_for_first_frame: false,
+            present_on_render: true,
+            pending_present: None,
+            #[cfg(feature = "egui")]
+            egui_painter: None,
+            #[cfg(feature = "egui")]
+            egui_frame: None,
+            #[cfg(feature = "svg")]
+            svg_textures: HashMap::new(),
+        }
+    }
+
+    pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
+        if new_size.width > 0 && new_size.height > 0 {
+            self.size = new_size;
+            self.config.width = new_size.width;
+            self.config.height = new_size.height;
+            self.surface.configure(&self.context.device, &self.config);
+            if let RenderTarget::Surface = self.target {
+                self.depth_texture = create_depth_texture(
+                    &self.context.device,
+                    self.target_size(),
+                    self.depth_format(),
+                );
+            }
+
+            if let Some(on_resize) = self.on_resize.as_mut() {
+                on_resize(new_size, self.config.format);
+            }
+        }
+    }
+
+    /// 请求把窗口内容区调整为给定的逻辑尺寸。
+    ///
+    /// 部分平台会同步返回新尺寸，此时立即重新配置表面；
+    /// 其余平台稍后通过 `WindowEvent::Resized` 走正常的 `resize()` 流程。
+    pub fn request_window_size(&mut self, width: f64, height: f64) {
+        if let Some(new_size) = self
+            .window
+            .request_inner_size(LogicalSize::new(width, height))
+        {
+            self.resize(new_size);
+        }
+    }
+
+    /// 捕获或释放鼠标，用于第一人称/环绕相机的鼠标视角控制。
+    ///
+    /// 捕获时隐藏光标，优先使用 `CursorGrabMode::Locked`（光标固定不动），
+    /// 平台不支持时退回 `Confined`（光标限制在窗口内）：
+    /// macOS 只支持 `Locked`，Windows 只支持 `Confined`，X11/Wayland 两者通常都支持。
+    /// 捕获期间通过 `take_mouse_delta` 读取原始的相对位移；
+    /// 窗口失去焦点或按下 Escape 时自动释放。
+    pub fn set_cursor_grab(&mut self, grab: bool) {
+        if grab {
+            let result = self
+                .window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined));
+            if let Err(e) = result {
+                eprintln!("Failed to grab cursor: {e}");
+                return;
+            }
+        } else {
+            let _ = self.window.set_cursor_grab(CursorGrabMode::None);
+        }
+        self.window.set_cursor_visible(!grab);
+        self.cursor_grabbed = grab;
+        self.mouse_delta = (0.0, 0.0);
+    }
+
+    pub fn cursor_grabbed(&self) -> bool {
+        self.cursor_grabbed
+    }
+
+    // 累加 `DeviceEvent::MouseMotion` 的原始位移，仅在捕获鼠标时记录
+    pub(crate) fn handle_mouse_motion(&mut self, delta: (f64, f64)) {
+        if self.cursor_grabbed {
+            self.mouse_delta.0 += delta.0;
+            self.mouse_delta.1 += delta.1;
+        }
+    }
+
+    /// 取出自上次调用以来累计的鼠标相对位移并清零
+    pub fn take_mouse_delta(&mut self) -> (f64, f64) {
+        std::mem::take(&mut self.mouse_delta)
+    }
+
+    /// 注册窗口尺寸变化的回调，在表面重新配置之后调用，
+    /// 参数为新的物理尺寸和表面格式，用于重建与分辨率相关的用户资源
+    pub fn set_on_resize(
+        &mut self,
+        callback: impl FnMut(PhysicalSize<u32>, TextureFormat) + 'static,
+    ) {
+        self.on_resize = Some(Box::new(callback));
+    }
+
+    /// 添加一个带索引的网格，与已有网格一起绘制
+    pub fn add_mesh(&mut self, vertices: &[Vertex], indices: &[u16]) -> MeshId {
+        let id = self.add_mesh_with_pipeline(PipelineHandle::default(), vertices, indices);
+        if all_back_facing(vertices, indices) {
+            eprintln!(
+                "Mesh {id:?} is entirely back-facing and will be culled; \
+                 its winding is probably clockwise, try Renderer::flip_winding"
+            );
+        }
+        id
+    }
+
+    /// 添加一个使用指定着色器程序绘制的网格。
+    ///
+    /// 顶点类型 `V` 的内存布局必须与注册程序时给出的顶点布局一致。
+    pub fn add_mesh_with_pipeline<V: bytemuck::Pod>(
+        &mut self,
+        pipeline: PipelineHandle,
+        vertices: &[V],
+        indices: &[u16],
+    ) -> MeshId {
+        assert!(
+            pipeline.0 < self.programs.len(),
+            "unknown pipeline handle {pipeline:?}"
+        );
+        self.meshes.push(Mesh::new(
+            &self.context.device,
+            pipeline,
+            bytemuck::cast_slice(vertices),
+            indices,
+        ));
+        MeshId(self.meshes.len() - 1)
+    }
+
+    /// 注册一个使用自定义顶点布局的着色器程序，例如 `position + uv + normal + tangent`。
+    ///
+    /// 着色器需要提供 `vs_main` 和 `fs_main` 入口，顶点输入的 location 和格式
+    /// 必须与 `vertex_layout` 一致；不一致时 wgpu 的校验会失败，此处返回错误信息，
+    /// 不注册程序。与内置程序一样受深度比较和手动 sRGB（需要声明
+    /// `override MANUAL_SRGB: bool`）等设置影响；背面调试和正反面调试只作用于
+    /// 内置程序，开启时自定义程序的网格照常绘制。
+    pub fn register_pipeline(
+        &mut self,
+        name: &str,
+        wgsl: &str,
+        vertex_layout: VertexBufferLayout<'static>,
+    ) -> Result<PipelineHandle, String> {
+        self.register_pipeline_with_bind_groups(name, wgsl, vertex_layout, &[])
+    }
+
+    /// 同 `register_pipeline`，并为程序声明自己的绑定组布局，
+    /// 第 i 个布局对应着色器中的 `@group(i)`。
+    ///
+    /// 自定义程序的所有组索引都归用户使用，wzui 不会在其中绑定任何资源。
+    /// 绑定组用 `create_bind_group` 创建、`set_bind_group` 挂到程序上；
+    /// 在所有组都设置好之前，该程序的网格不会被绘制。
+    pub fn register_pipeline_with_bind_groups(
+        &mut self,
+        name: &str,
+        wgsl: &str,
+        vertex_layout: VertexBufferLayout<'static>,
+        bind_group_layouts: &[&BindGroupLayout],
+    ) -> Result<PipelineHandle, String> {
+        let handle = PipelineHandle(self.programs.len());
+        let key = self.pipeline_key.for_program(handle);
+
+        self.context
+            .device
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = self
+            .context
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(name),
+                source: wgpu::ShaderSource::Wgsl(wgsl.into()),
+            });
+        let layout = self
+            .context
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(name),
+                bind_group_layouts,
+                push_constant_ranges: &[],
+            });
+        let program = Program {
+            label: name.to_owned(),
+            shader,
+            layout,
+            vertex_layout,
+            constants: BTreeMap::new(),
+            bind_groups: vec![None; bind_group_layouts.len()],
+            source_path: None,
+        };
+        let pipeline =
+            create_render_pipeline(&self.context.device, &program, self.target_format(), key);
+        if let Some(error) = pollster::block_on(self.context.device.pop_error_scope()) {
+            return Err(error.to_string());
+        }
+
+        self.programs.push(program);
+        self.pipelines.insert(key, pipeline);
+        self.update_pipeline();
+        Ok(handle)
+    }
+
+    /// 按给定布局创建绑定组，用于 `set_bind_group`。
+    ///
+    /// 布局可以用 `device_context().device()` 创建，
+    /// 需要与注册程序时对应组索引的布局一致。
+    pub fn create_bind_group(
+        &mut self,
+        layout: &BindGroupLayout,
+        entries: &[BindGroupEntry],
+    ) -> BindGroupHandle {
+        let bind_group = self
+            .context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Custom Bind Group"),
+                layout,
+                entries,
+            });
+        self.bind_groups.push(bind_group);
+        BindGroupHandle(self.bind_groups.len() - 1)
+    }
+
+    /// 把绑定组挂到自定义程序的 `@group(index)` 上，之后该程序的每次绘制都会绑定它。
+    ///
+    /// # Panics
+    ///
+    /// 句柄无效，或 `index` 超出注册程序时声明的绑定组布局数量时 panic。
+    pub fn set_bind_group(&mut self, pipeline: PipelineHandle, index: u32, group: BindGroupHandle) {
+        let bind_group = self.bind_groups[group.0].clone();
+        let program = &mut self.programs[pipeline.0];
+        let slot = program
+            .bind_groups
+            .get_mut(index as usize)
+            .expect("bind group index not declared by the pipeline layout");
+        *slot = Some(bind_group);
+    }
+
+    /// 添加一个无索引的三角形汤网格，先去除重复顶点并生成索引。
+    ///
+    /// 返回网格句柄和缩减比例（去重后顶点数 / 原始顶点数），
+    /// 例如两个三角形拼成的正方形由 6 个顶点变为 4 个，比例约为 0.67。
+    /// 输入为空时比例为 1.0，添加的空网格不会被绘制。
+    pub fn add_mesh_deduplicated(&mut self, vertices: &[Vertex]) -> (MeshId, f32) {
+        let (unique, indices) = deduplicate_vertices(vertices);
+        let ratio = if vertices.is_empty() {
+            1.0
+        } else {
+            unique.len() as f32 / vertices.len() as f32
+        };
+        (self.add_mesh(&unique, &indices), ratio)
+    }
+
+    /// 添加一个无索引网格：顶点按顺序每三个组成一个三角形（或按当前拓扑解释），
+    /// 绘制时使用 `draw` 而不是 `draw_indexed`。
+    ///
+    /// 适合点云、程序生成的几何体等没有共享顶点的数据，不必再生成
+    /// `0, 1, 2, ...` 这样的平凡索引，顶点数也不受 16 位索引的限制。
+    /// 需要去重时改用 `add_mesh_deduplicated`。
+    pub fn add_mesh_vertices_only(&mut self, vertices: &[Vertex]) -> MeshId {
+        self.meshes.push(Mesh::new_non_indexed(
+            &self.context.device,
+            PipelineHandle::default(),
+            bytemuck::cast_slice(vertices),
+            size_of::<Vertex>(),
+        ));
+        MeshId(self.meshes.len() - 1)
+    }
+
+    /// 设置网格的颜色调制：在顶点着色器中与每个顶点的颜色逐分量相乘，
+    /// 传 `None` 恢复原有颜色。
+    ///
+    /// 通过 uniform 实现，不修改也不重新上传顶点数据，适合高亮选中的模型或
+    /// 按主题改色。只作用于内置程序的网格；自定义程序的绑定组由用户管理，
+    /// 对其网格调用没有效果。线框模式下同样生效。
+    ///
+    /// # Panics
+    ///
+    /// `mesh` 超出当前场景的网格数时 panic，例如 `reset` 之前返回的部分句柄。
+    pub fn set_mesh_tint(&mut self, mesh: MeshId, tint: Option<[f32; 3]>) {
+        self.meshes[mesh.0].set_tint(
+            &self.context.device,
+            &self.context.queue,
+            &self.tint_layout,
+            tint,
+        );
+    }
+
+    /// 把场景恢复到刚创建时的状态，用于“新建场景”或在测试用例之间隔离状态。
+    ///
+    /// 删除所有网格并重新放入默认的正方形，恢复默认清屏颜色，取消渐变、动画背景、
+    /// 暗角和固定宽高比，恢复每帧清屏（关闭 `set_clear_on_present_only`），
+    /// 清空本帧排队的纹理绘制，把动画时间归零，释放已加载的 SVG 纹理。
+    /// 之前返回的 `MeshId` 全部失效：不要再传给 `set_mesh_tint` 或 `flip_winding`，
+    /// 超出新场景网格数的句柄会导致 panic，其余的则指向新场景中的网格。
+    ///
+    /// 以下渲染器设置保持不变：渲染目标、已注册的着色器程序和绑定组、
+    /// 管线选项（深度比较、拓扑、深度预渲染、背面/正反面调试、线框、手动 sRGB）、
+    /// 深度可视化、时间缩放和固定步长、鼠标捕获、尺寸变化回调、
+    /// `set_wait_for_first_frame`、`set_present_on_render` 以及 CPU 计时数据。
+    pub fn reset(&mut self) {
+        self.meshes = vec![default_mesh(&self.context.device)];
+        self.clear_color = DEFAULT_CLEAR_COLOR;
+        self.background_gradient = None;
+        self.animated_background = None;
+        self.target_aspect = None;
+        self.letterbox_color = Color::BLACK;
+        self.vignette = None;
+        self.clear_on_present_only = false;
+        self.blit_draws.clear();
+        let (time_scale, fixed_step) = (self.clock.time_scale(), self.clock.fixed_step());
+        self.clock = AnimationClock::new();
+        self.clock.set_time_scale(time_scale);
+        self.clock.set_fixed_step(fixed_step);
+        #[cfg(feature = "svg")]
+        self.svg_textures.clear();
+    }
+
+    /// 反转网格的环绕方向：交换每个三角形的第 2、3 个索引并重新上传索引缓冲区。
+    ///
+    /// 用于修复顺时针环绕的导入模型，使其正面在默认的 CCW 设置下不被剔除。
+    /// `add_mesh` 检测到网格全部背向屏幕时会打印提示。只适用于三角形列表。
+    /// 无索引网格交换每个三角形的第 2、3 个顶点。
+    ///
+    /// # Panics
+    ///
+    /// `mesh` 超出当前场景的网格数时 panic，例如 `reset` 之前返回的部分句柄。
+    pub fn flip_winding(&mut self, mesh: MeshId) {
+        self.meshes[mesh.0].flip_winding(&self.context.device);
+    }
+
+    /// 检查内置程序的每个网格，背向屏幕的三角形多于朝向屏幕的三角形时
+    /// 自动反转其环绕方向，返回被反转的网格。
+    ///
+    /// 按顶点在裁剪空间中的位置判断朝向，适合在加载外部模型后调用一次，
+    /// 让“模型加载后看不见”的情况直接恢复正常。只在三角形列表拓扑下生效，
+    /// 其他拓扑返回空列表；自定义程序的顶点布局未知，不做检查。
+    pub fn auto_fix_winding(&mut self) -> Vec<MeshId> {
+        if self.pipeline_key.topology != PrimitiveTopology::TriangleList {
+            return Vec::new();
+        }
+        let mut flipped = Vec::new();
+        for (index, mesh) in self.meshes.iter_mut().enumerate() {
+            if mesh.program == PipelineHandle::default() && mesh.mostly_back_facing() {
+                mesh.flip_winding(&self.context.device);
+                flipped.push(MeshId(index));
+            }
+        }
+        flipped
+    }
+
+    /// 跳过每帧清屏，颜色附件改用 `LoadOp::Load`。
+    ///
+    /// 仅当场景保证每帧覆盖整个画面时（例如铺满的背景图或渐变）才应开启，
+    /// 否则未被覆盖的区域会显示上一帧残留或未定义的垃圾像素。默认关闭（`Clear`）。
+    pub fn set_clear_on_present_only(&mut self, enabled: bool) {
+        self.clear_on_present_only = enabled;
+    }
+
+    /// 设置清屏颜色（线性空间），默认为深蓝色。会取消渐变背景。
+    pub fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = color;
+        self.background_gradient = None;
+    }
+
+    /// 用十六进制字符串设置清屏颜色，接受 `#RRGGBB` 或 `#RRGGBBAA`，
+    /// `#` 可省略，不区分大小写。
+    ///
+    /// 字符串按 sRGB 解释（与取色器、CSS 一致），转换为线性空间后交给
+    /// `set_clear_color`。格式错误时保持原来的颜色不变。
+    pub fn set_clear_color_hex(&mut self, hex: &str) -> Result<(), ParseColorError> {
+        self.set_clear_color(parse_hex_color(hex)?);
+        Ok(())
+    }
+
+    /// 使用从上到下的竖直渐变作为背景（线性空间），常见于 3D 查看器。
+    ///
+    /// `LoadOp::Clear` 只能填充单色，因此渐变通过在场景之前绘制一个
+    /// 不测试深度的全屏三角形实现。与纯色背景互斥：调用 `set_clear_color`
+    /// 或开启 `set_animated_background` 会取消渐变，反之亦然。
+    pub fn set_background_gradient(&mut self, top: Color, bottom: Color) {
+        self.background_gradient = Some([top, bottom]);
+        self.animated_background = None;
+    }
+
+    /// 固定内容的宽高比（宽 / 高），例如 `Some(16.0 / 9.0)`；`None` 铺满整个目标（默认）。
+    ///
+    /// 设置后场景绘制在居中的、符合该比例的视口内，其余区域填充 `set_letterbox_color`
+    /// 设置的颜色，窗口比例变化时内容不会被拉伸。视口每帧按目标尺寸计算，
+    /// 窗口缩放后自动更新。纹理绘制和 egui 仍然覆盖整个目标。
+    pub fn set_target_aspect(&mut self, aspect: Option<f32>) {
+        self.target_aspect = aspect.filter(|&a| a > 0.0 && a.is_finite());
+    }
+
+    /// 设置信箱模式下黑边的颜色（线性空间），默认黑色
+    pub fn set_letterbox_color(&mut self, color: Color) {
+        self.letterbox_color = color;
+    }
+
+    /// 开启后清屏颜色随动画时钟在色相环上循环，每 `period_secs` 秒转一圈。
+    ///
+    /// 受 `set_time_scale` 影响，暂停时颜色也停住；关闭后恢复 `set_clear_color` 设置的颜色。
+    /// 开启时会取消渐变背景。
+    pub fn set_animated_background(&mut self, on: bool, period_secs: f32) {
+        self.animated_background = (on && period_secs > 0.0).then_some(period_secs);
+        if self.animated_background.is_some() {
+            self.background_gradient = None;
+        }
+    }
+
+    /// 在场景之上叠加暗角：按到画面中心的距离把边角逐渐压暗。
+    ///
+    /// `intensity` 为角落处的压暗程度，0 关闭、1 为全黑；`radius` 为开始压暗的距离，
+    /// 0 为中心、1 为角落。在场景之后、纹理绘制和 UI 之前绘制，
+    /// 固定宽高比时只作用于内容区域。
+    pub fn set_vignette(&mut self, intensity: f32, radius: f32) {
+        let intensity = intensity.clamp(0.0, 1.0);
+        // smoothstep 要求下界小于上界 1
+        self.vignette = (intensity > 0.0).then_some([intensity, radius.clamp(0.0, 0.99)]);
+    }
+
+    /// 在下一次 `render()` 中把纹理绘制到目标的矩形区域 `rect = [x, y, 宽, 高]`，
+    /// 单位为物理像素，原点在左上角。
+    ///
+    /// 用于调试离屏渲染目标：把阴影贴图、模糊缓冲区等中间结果缩放显示在屏幕一角。
+    /// 在场景之后、UI 之前绘制，最近邻采样；纹理需带 `TEXTURE_BINDING` 用途且为
+    /// 颜色格式（深度纹理不支持）。超出目标的部分被裁掉。只作用于一帧，需要每帧调用。
+    pub fn draw_texture(&mut self, view: &TextureView, rect: [f32; 4]) {
+        self.blit_draws.push(BlitDraw {
+            view: view.clone(),
+            rect,
+            uv_rect: [0.0, 0.0, 1.0, 1.0],
+            tint: Color::WHITE,
+            blend: false,
+        });
+    }
+
+    /// 在下一次 `render()` 中把纹理的一块区域绘制到目标矩形 `dest_rect = [x, y, 宽, 高]`
+    /// （物理像素，原点在左上角），用于精灵表和图标图集。
+    ///
+    /// `uv_rect = [u, v, 宽, 高]` 是归一化的纹理坐标，原点在纹理左上角；
+    /// `tint` 为线性空间颜色，与采样结果逐分量相乘，`Color::WHITE` 表示不着色。
+    /// 与 `draw_texture` 不同，结果按 alpha 与已有内容混合。与 `draw_texture`
+    /// 共用同一队列，按调用顺序绘制，同样是最近邻采样、只作用于一帧。
+    pub fn draw_textured_rect(
+        &mut self,
+        view: &TextureView,
+        dest_rect: [f32; 4],
+        uv_rect: [f32; 4],
+        tint: Color,
+    ) {
+        self.blit_draws.push(BlitDraw {
+            view: view.clone(),
+            rect: dest_rect,
+            uv_rect,
+            tint,
+            blend: true,
+        });
+    }
+
+    /// 设置动画时间缩放：0.5 为半速，2.0 为两倍速，0 为暂停（负值按 0 处理）。
+    ///
+    /// 缩放作用在动画时钟的累加上，所有随时间变化的效果一起变速。
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.clock.set_time_scale(scale);
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.clock.time_scale()
+    }
+
+    /// 设置固定步长（秒）进入逐帧模式，`None` 恢复跟随真实时间。
+    ///
+    /// 逐帧模式下动画时钟不再随 `render()` 前进，只在调用 `step_frame` 时前进
+    /// `dt`（乘以时间缩放），因此相同的调用序列总是得到相同的画面，可用于逐帧
+    /// 检查动画或生成确定性的基准图像。`App` 在此模式下不再连续重绘，
+    /// 只在窗口需要刷新或 `step_frame` 时重绘。
+    ///
+    /// `App` 没有内置的单步按键；按键单步需在 `App::set_on_key_press`
+    /// 的回调中调用 `step_frame`。
+    pub fn set_fixed_step(&mut self, dt: Option<f32>) {
+        self.clock.set_fixed_step(dt);
+        // 退出逐帧模式时重新启动 App 的连续重绘
+        self.window.request_redraw();
+    }
+
+    /// 当前的固定步长，`None` 表示动画跟随真实时间
+    pub fn fixed_step(&self) -> Option<f32> {
+        self.clock.fixed_step()
+    }
+
+    /// 在逐帧模式下把动画时钟前进一个固定步长并请求重绘窗口；
+    /// 不在逐帧模式时没有效果。
+    ///
+    /// 离屏使用（例如测试中渲染到纹理目标）时在之后自行调用 `render()`。
+    pub fn step_frame(&mut self) {
+        if self.clock.fixed_step().is_some() {
+            self.clock.step();
+            self.window.request_redraw();
+        }
+    }
+
+    /// 动画时钟累计的时间（秒），已计入时间缩放
+    pub fn animation_time(&self) -> f32 {
+        self.clock.elapsed()
+    }
+
+    /// 开始一个名为 `name` 的 CPU 计时范围，返回的守卫被丢弃时记录经过的时间。
+    ///
+    /// ```ignore
+    /// {
+    ///     let _cull = renderer.scope("cull");
+    ///     // ... 剔除 ...
+    /// }
+    /// renderer.render()?;
+    /// ```
+    ///
+    /// 计时归入当前帧；`render()` 开始时结束这一帧，因此包住 `render()`
+    /// 本身的范围会计入下一帧。
+    pub fn scope(&self, name: &str) -> ProfileScope {
+        self.profiler.scope(name)
+    }
+
+    /// 上一帧各计时范围的耗时（毫秒）。`render()` 自身的耗时记为 `"render"`
+    pub fn cpu_profile(&self) -> HashMap<String, f32> {
+        self.profiler.last_frame().clone()
+    }
+
+    /// 开启后在下一次呈现到表面之后调用 `window.set_visible(true)`。
+    ///
+    /// 配合以 `with_visible(false)` 创建的窗口使用：窗口在第一帧画好之前保持隐藏，
+    /// 避免启动时的白屏闪烁。`App` 默认会这样做，见 `AppConfig::show_immediately`。
+    pub fn set_wait_for_first_frame(&mut self, wait: bool) {
+        self.wait_for_first_frame = wait;
+    }
+
+    /// 设置 `render()` 是否在提交后立即呈现（默认 `true`）。
+    ///
+    /// 关闭后的生命周期：`render()` 照常录制并提交命令，但把交换链纹理留在
+    /// Renderer 中；调用方用 `take_present_token` 取走这一帧，在合适的时机
+    /// （例如外部垂直同步或音频时钟）调用 `present`。下一次 `render()` 之前
+    /// 必须呈现或丢弃上一个令牌，因为交换链同一时间只能借出一张纹理；
+    /// 没有取走的令牌会在下一次 `render()` 开始时自动呈现。
+    /// 只影响窗口表面，纹理目标没有呈现步骤。
+    pub fn set_present_on_render(&mut self, on: bool) {
+        self.present_on_render = on;
+        if on && let Some(texture) = self.pending_present.take() {
+            self.present(PresentToken(texture));
+        }
+    }
+
+    /// 取走最近一次 `render()` 绘制、尚未呈现的帧；自动呈现开启时总是返回 `None`
+    pub fn take_present_token(&mut self) -> Option<PresentToken> {
+        self.pending_present.take().map(PresentToken)
+    }
+
+    /// 呈现 `take_present_token` 取得的帧
+    pub fn present(&mut self, token: PresentToken) {
+        token.0.present();
+        if std::mem::take(&mut self.wait_for_first_frame) {
+            self.window.set_visible(true);
+        }
+    }
+
+    /// 切换渲染目标。场景和 UI 都会绘制到新的目标上。
+    ///
+    /// 目标为纹理时，`render()` 跳过表面的获取与呈现，直接绘制到该纹理。
+    /// 纹理格式与当前管线不同时会重建管线。
+    ///
+    /// # Panics
+    ///
+    /// 目标纹理没有 `RENDER_ATTACHMENT` 用途时 panic。
+    pub fn set_target(&mut self, target: RenderTarget) {
+        if let RenderTarget::Texture(texture) = &target {
+            assert!(
+                texture.usage().contains(TextureUsages::RENDER_ATTACHMENT),
+                "render target texture must have RENDER_ATTACHMENT usage"
+            );
+        }
+
+        let old_format = self.target_format();
+        self.target = target;
+        self.depth_texture = create_depth_texture(
+            &self.context.device,
+            self.target_size(),
+            self.depth_format(),
+        );
+        let new_format = self.target_format();
+        if new_format != old_format {
+            // 缓存的管线都是按旧格式创建的
+            self.pipelines.clear();
+            self.pipeline_key.manual_srgb = self.manual_srgb && !new_format.is_srgb();
+            self.update_pipeline();
+            self.background_painter = None;
+            self.vignette_painter = None;
+            self.blit_painter = None;
+            self.depth_debug_painter = None;
+            // egui 的纹理只上传一次，保留画家，只重建其管线
+            #[cfg(feature = "egui")]
+            if let Some(painter) = &mut self.egui_painter {
+                painter.set_format(&self.context.device, new_format);
+            }
+        }
+    }
+
+    /// 本 Renderer 使用的设备上下文，传给 `new_with_context` 即可让其他窗口共享同一个设备
+    pub fn device_context(&self) -> DeviceContext {
+        self.context.clone()
+    }
+
+    /// 当前使用的图形后端
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// 适配器支持的全部特性，可用于在设置界面中禁用不受支持的选项
+    pub fn adapter_features(&self) -> wgpu::Features {
+        self.context.adapter_features()
+    }
+
+    /// 适配器名称、后端、驱动及常用可选特性（push constants、线框多边形模式、
+    /// 时间戳查询、纹理压缩等）是否受支持的文本报告
+    pub fn adapter_report(&self) -> String {
+        self.context.adapter_report()
+    }
+
+    /// 当前渲染目标格式与深度格式都支持的最大多重采样数，设置界面可以据此只提供
+    /// 有效的 MSAA 级别。总是至少为 1。
+    ///
+    /// 只是查询：Renderer 本身目前总是单采样渲染，没有可设置的采样数。
+    pub fn max_supported_sample_count(&self) -> u32 {
+        let supported = |format: TextureFormat| {
+            self.context
+                .adapter
+                .get_texture_format_features(format)
+                .flags
+                .supported_sample_counts()
+        };
+        let depth_counts = supported(self.depth_format());
+        supported(self.target_format())
+            .into_iter()
+            .filter(|count| depth_counts.contains(count))
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// 窗口表面在当前适配器上支持的呈现模式，设置界面可以只列出这些选项。
+    ///
+    /// `Fifo`（垂直同步）总是可用，也是默认模式。
+    pub fn supported_present_modes(&self) -> Vec<PresentMode> {
+        self.surface
+            .get_capabilities(&self.context.adapter)
+            .present_modes
+    }
+
+    /// 估算 Renderer 持有的 GPU 资源占用的内存：网格缓冲区、深度缓冲区、
+    /// 纹理渲染目标和 UI 纹理。交换链纹理由系统管理，不计入。
+    pub fn memory_report(&self) -> MemoryReport {
+        let buffer_bytes = self.meshes.iter().map(Mesh::buffer_bytes).sum();
+        let mut texture_bytes = memory::texture_bytes(&self.depth_texture);
+        if let RenderTarget::Texture(texture) = &self.target {
+            texture_bytes += memory::texture_bytes(texture);
+        }
+        #[cfg(feature = "egui")]
+        if let Some(painter) = &self.egui_painter {
+            texture_bytes += painter.texture_bytes();
+        }
+        #[cfg(feature = "svg")]
+        {
+            texture_bytes += self
+                .svg_textures
+                .values()
+                .map(memory::texture_bytes)
+                .sum::<u64>();
+        }
+        MemoryReport {
+            buffer_bytes,
+            texture_bytes,
+        }
+    }
+
+    /// 深度缓冲区实际使用的格式
+    pub fn depth_format(&self) -> TextureFormat {
+        self.pipeline_key.depth_format
+    }
+
+    /// 当前是否在着色器中手动做 sRGB 编码
+    pub fn manual_srgb_active(&self) -> bool {
+        self.pipeline_key.manual_srgb
+    }
+
+    /// 设置深度比较函数（默认 `Less`）。
+    ///
+    /// 多遍渲染技术常用其他比较函数，例如在远平面绘制天空盒时使用 `LessEqual`。
+    /// 每种比较函数的管线只创建一次并缓存。
+    pub fn set_depth_compare(&mut self, func: CompareFunction) {
+        self.pipeline_key.depth_compare = func;
+        self.update_pipeline();
+    }
+
+    /// 设置图元拓扑（默认 `TriangleList`），对所有网格生效。
+    ///
+    /// `TriangleStrip`/`LineStrip` 会自动设置 `strip_index_format`，
+    /// 索引中的 `STRIP_RESTART_INDEX` 断开条带，多条条带可以放在同一个索引缓冲区里。
+    /// 重心坐标线框只支持 `TriangleList`，其他拓扑下照常绘制。
+    pub fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        self.pipeline_key.topology = topology;
+        self.update_pipeline();
+    }
+
+    /// 开启深度预渲染：先只写深度渲染一遍几何体，颜色遍再以 `Equal`
+    /// 比较且不写深度的方式绘制，每个像素只执行一次片元着色器。
+    ///
+    /// 适合重叠严重、片元着色开销大的场景；开启期间 `set_depth_compare`
+    /// 只作用于预渲染遍。
+    pub fn set_depth_prepass(&mut self, on: bool) {
+        self.pipeline_key.depth_prepass = on;
+        self.update_pipeline();
+    }
+
+    /// 开启后关闭背面剔除，并把背面三角形染成品红色，用于排查模型中
+    /// 环绕方向或法线反了的面。只作用于内置程序的网格，
+    /// `register_pipeline` 注册的程序照常绘制。
+    pub fn set_backface_debug(&mut self, on: bool) {
+        self.pipeline_key.backface_debug = on;
+        self.update_pipeline();
+    }
+
+    /// 开启重心坐标线框：在三角形边缘叠加指定颜色的线，线宽 `thickness` 以像素为单位。
+    ///
+    /// 不依赖 `PolygonMode::Line` 特性，所有后端都可用，线宽可调且带抗锯齿。
+    /// 开启时网格按三角形展开为无索引顶点绘制，并关闭背面剔除。
+    /// 只作用于内置程序的网格，`register_pipeline` 注册的程序照常绘制。
+    pub fn set_barycentric_wireframe(&mut self, on: bool, thickness: f32, color: [f32; 3]) {
+        self.pipeline_key.wireframe = on.then(|| Wireframe::new(thickness, color));
+        self.update_pipeline();
+    }
+
+    /// 开启后关闭背面剔除，正面染成绿色、背面染成红色，一眼看出导入模型中
+    /// 里外翻转的部分。开启期间覆盖网格原本的颜色，并优先于 `set_backface_debug`。
+    /// 只作用于内置程序的网格，`register_pipeline` 注册的程序照常绘制。
+    pub fn set_face_debug(&mut self, on: bool) {
+        self.pipeline_key.face_debug = on;
+        self.update_pipeline();
+    }
+
+    /// 开启后把深度缓冲区以灰度显示在整个画面上：近处为黑，远平面及未绘制的区域为白。
+    ///
+    /// 用于排查深度精度、深度比较和裁剪面问题。显示的是归一化的深度值本身；
+    /// 内置程序直接输出裁剪空间坐标（w 为 1），深度已经是线性的，因此不再做
+    /// 线性化，使用透视投影的自定义程序看到的是非线性深度。
+    /// 纹理绘制和 UI 仍显示在其上。
+    pub fn set_depth_debug(&mut self, on: bool) {
+        self.depth_debug = on;
+    }
+
+    /// 离屏连续渲染 `frames` 帧并统计帧时间，用于 CI 中跟踪性能回归。
+    ///
+    /// 渲染到与当前目标同尺寸、同格式的离屏纹理，不获取也不呈现表面；
+    /// 每帧等待 GPU 完成，因此 `frame` 统计包含 GPU 耗时。结束后恢复原渲染目标。
+    pub fn benchmark(&mut self, frames: u32) -> BenchmarkReport {
+        let texture = self.create_offscreen_target("Benchmark Target", TextureUsages::empty());
+        let previous_target = std::mem::replace(&mut self.target, RenderTarget::Surface);
+        self.set_target(RenderTarget::Texture(texture));
+
+        let mut cpu_times = Vec::with_capacity(frames as usize);
+        let mut frame_times = Vec::with_capacity(frames as usize);
+        for _ in 0..frames {
+            let start = Instant::now();
+            // 纹理目标不会返回表面错误
+            let _ = self.render();
+            cpu_times.push(start.elapsed());
+            self.wait_idle();
+            frame_times.push(start.elapsed());
+        }
+
+        self.set_target(previous_target);
+
+        BenchmarkReport {
+            frames,
+            cpu: FrameStats::from_samples(&cpu_times),
+            frame: FrameStats::from_samples(&frame_times),
+        }
+    }
+
+    /// 把当前画面复制到系统剪贴板，可以直接粘贴到聊天或缺陷报告中。
+    ///
+    /// 离屏渲染一帧到与当前目标同尺寸、同格式的纹理，读回像素后以 RGBA8 图像
+    /// 写入剪贴板；不获取也不呈现表面。只支持 8 位 RGBA/BGRA 格式的目标。
+    #[cfg(feature = "clipboard")]
+    pub fn copy_frame_to_clipboard(&mut self) -> Result<(), ClipboardError> {
+        let texture = self.create_offscreen_target("Clipboard Target", TextureUsages::COPY_SRC);
+        let previous_target = std::mem::replace(&mut self.target, RenderTarget::Surface);
+        self.set_target(RenderTarget::Texture(texture.clone()));
+        // 纹理目标不会返回表面错误
+        let _ = self.render();
+        self.set_target(previous_target);
+
+        let pixels = read_texture(&self.context.device, &self.context.queue, &texture);
+        clipboard::set_image(
+            pixels,
+            [texture.width(), texture.height()],
+            texture.format(),
+        )
+    }
+
+    /// 把 SVG 文件栅格化为纹理，较长的一边为 `size` 像素，保持宽高比。
+    ///
+    /// 返回的纹理视图可以交给 `draw_textured_rect` 绘制 UI 图标。结果按路径和
+    /// 尺寸缓存，同一尺寸重复调用不会重新读取文件；请求其他尺寸（例如窗口缩放
+    /// 后需要更大的图标）时重新栅格化，保证任意尺寸下都清晰。
+    /// 不支持 SVG 中的文字元素。
+    #[cfg(feature = "svg")]
+    pub fn load_svg(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        size: u32,
+    ) -> Result<TextureView, SvgError> {
+        let key = (path.as_ref().to_path_buf(), size);
+        let texture = match self.svg_textures.get(&key) {
+            Some(texture) => texture,
+            None => {
+                let texture =
+                    svg::rasterize(&self.context.device, &self.context.queue, &key.0, size)?;
+                self.svg_textures.entry(key).or_insert(texture)
+            }
+        };
+        Ok(texture.create_view(&TextureViewDescriptor::default()))
+    }
+
+    /// 读回上一帧在像素 `(x, y)` 处的归一化深度值（0 为近平面，1 为远平面或未绘制）。
+    ///
+    /// 坐标以渲染目标的物理像素为单位，原点在左上角。超出范围，或深度格式
+    /// 不可拷贝（`Depth24Plus`、`Depth24PlusStencil8`）时返回 `None`。
+    /// 结合相机的逆投影即可得到光标下表面的世界坐标。会阻塞等待 GPU 完成拷贝，
+    /// 适合点击等偶发操作，不宜每帧调用。
+    pub fn read_depth_at(&self, x: u32, y: u32) -> Option<f32> {
+        let [width, height] = self.target_size();
+        if x >= width || y >= height {
+            return None;
+        }
+        read_depth_texel(
+            &self.context.device,
+            &self.context.queue,
+            &self.depth_texture,
+            [x, y],
+        )
+    }
+
+    /// 用新的 WGSL 源码构建候选管线，只有完全成功时才替换当前管线。
+    ///
+    /// 着色器编译或管线创建失败时返回错误信息，原有管线保持不变，
+    /// 适合实时编辑着色器时使用。手动 sRGB 模式下新着色器需要声明
+    /// `override MANUAL_SRGB: bool`；`set_shader_constant` 设置过的常量会沿用，
+    /// 新着色器同样需要声明。`@group(0) @binding(0)` 为网格颜色调制
+    /// （`var<uniform> mesh_tint: vec4<f32>`），新着色器可以选择是否使用。
+    pub fn hot_swap_pipeline(&mut self, new_shader_src: &str) -> Result<(), String> {
+        self.context
+            .device
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = self
+            .context
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Shader"),
+                source: wgpu::ShaderSource::Wgsl(new_shader_src.into()),
+            });
+        let program = Program {
+            shader,
+            ..self.programs[0].clone()
+        };
+        self.replace_program(PipelineHandle::default(), program)
+    }
+
+    /// 用程序生成或编辑过的 WGSL 源码替换内置着色器，是基于文件的
+    /// `reload_all_pipelines` 的字符串版本。
+    ///
+    /// 与 `hot_swap_pipeline` 相同：编译或管线创建失败时返回错误信息并保留原有管线。
+    pub fn set_shader(&mut self, wgsl: &str) -> Result<(), String> {
+        self.hot_swap_pipeline(wgsl)
+    }
+
+    /// 设置内置着色器中 WGSL override 常量的值（例如 `override scale: f32 = 1.0;`），
+    /// 并用新的常量表重建管线，无需修改 WGSL 源码。
+    ///
+    /// 常量对顶点和片元阶段都生效，值按常量的类型转换（bool 以非零为 true）。
+    /// 着色器未声明该常量或值无法转换时返回错误信息，原有管线和常量保持不变。
+    pub fn set_shader_constant(&mut self, name: &str, value: f64) -> Result<(), String> {
+        let mut program = self.programs[0].clone();
+        program.constants.insert(name.to_owned(), value);
+        self.context
+            .device
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+        self.replace_program(PipelineHandle::default(), program)
+    }
+
+    /// 把着色器程序关联到一个 WGSL 源文件，之后 `reload_all_pipelines` 会从该文件
+    /// 重新编译它。`PipelineHandle::default()` 表示内置程序。
+    ///
+    /// 只记录路径，不会立即读取文件。
+    pub fn set_pipeline_source_path(
+        &mut self,
+        pipeline: PipelineHandle,
+        path: impl Into<std::path::PathBuf>,
+    ) {
+        self.programs[pipeline.0].source_path = Some(path.into());
+    }
+
+    /// 从源文件重新编译所有关联了源文件的着色器程序（见 `set_pipeline_source_path`）。
+    ///
+    /// 返回值按管线句柄的编号排列，每个已注册的程序一项；没有源文件的程序不变，
+    /// 结果为 `Ok(())`。读取或编译失败的程序保留原有管线，对应项为错误信息，
+    /// 便于在界面上标出哪些着色器有错误。已提交的帧仍使用旧管线，不受影响。
+    pub fn reload_all_pipelines(&mut self) -> Vec<Result<(), String>> {
+        (0..self.programs.len())
+            .map(|index| {
+                let handle = PipelineHandle(index);
+                let Some(path) = self.programs[index].source_path.clone() else {
+                    return Ok(());
+                };
+                let source = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+                self.context
+                    .device
+                    .push_error_scope(wgpu::ErrorFilter::Validation);
+                let shader =
+                    self.context
+                        .device
+                        .create_shader_module(wgpu::ShaderModuleDescriptor {
+                            label: Some(&self.programs[index].label),
+                            source: wgpu::ShaderSource::Wgsl(source.into()),
+                        });
+                let program = Program {
+                    shader,
+                    ..self.programs[index].clone()
+                };
+                self.replace_program(handle, program)
+            })
+            .collect()
+    }
+
+    // 用候选程序构建当前需要的所有管线（包括深度预渲染遍），全部成功后
+    // 才替换该程序并作废其旧管线。
+    // 调用前需已 push 一个 Validation 错误作用域，此处负责 pop
+    fn replace_program(&mut self, handle: PipelineHandle, program: Program) -> Result<(), String> {
+        let format = self.target_format();
+        let pipelines: Vec<_> = self
+            .active_keys()
+            .into_iter()
+            .map(|key| {
+                let key = key.for_program(handle);
+                let pipeline = create_render_pipeline(&self.context.device, &program, format, key);
+                (key, pipeline)
+            })
+            .collect();
+        if let Some(error) = pollster::block_on(self.context.device.pop_error_scope()) {
+            return Err(error.to_string());
+        }
+
+        // 该程序其他键的缓存管线用的是旧着色器，全部作废
+        self.programs[handle.0] = program;
+        self.pipelines.retain(|key, _| key.program != handle);
+        self.pipelines.extend(pipelines);
+        self.update_pipeline();
+        Ok(())
+    }
+
+    // 渲染时用到的管线键：颜色遍，开启深度预渲染时还有预渲染遍
+    fn active_keys(&self) -> Vec<PipelineKey> {
+        let mut keys = vec![self.pipeline_key];
+        if self.pipeline_key.depth_prepass {
+            keys.push(self.prepass_key());
+        }
+        keys
+    }
+
+    // 确保每个着色器程序在当前管线键下的管线都已创建
+    fn update_pipeline(&mut self) {
+        let format = self.target_format();
+        let keys = self.active_keys();
+        for (index, program) in self.programs.iter().enumerate() {
+            for &key in &keys {
+                let key = key.for_program(PipelineHandle(index));
+                self.pipelines.entry(key).or_insert_with(|| {
+                    create_render_pipeline(&self.context.device, program, format, key)
+                });
+            }
+        }
+    }
+
+    // 创建与当前目标同尺寸、同格式的离屏颜色纹理，用于不呈现到屏幕的渲染
+    fn create_offscreen_target(&self, label: &str, extra_usage: TextureUsages) -> Texture {
+        let [width, height] = self.target_size();
+        self.context
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.target_format(),
+                usage: TextureUsages::RENDER_ATTACHMENT | extra_usage,
+                view_formats: &[],
+            })
+    }
+
+    // 信箱模式下内容区域 [x, y, 宽, 高]（物理像素），居中且符合目标宽高比
+    fn letterbox_rect(&self) -> Option<[u32; 4]> {
+        let aspect = self.target_aspect?;
+        Some(letterbox_rect(aspect, self.target_size()))
+    }
+
+    // 深度预渲染遍使用的管线键
+    fn prepass_key(&self) -> PipelineKey {
+        PipelineKey {
+            depth_only: true,
+            wireframe: None,
+            ..self.pipeline_key
+        }
+    }
+
+    // 当前渲染目标的尺寸（像素）
+    fn target_size(&self) -> [u32; 2] {
+        match &self.target {
+            RenderTarget::Surface => [self.config.width, self.config.height],
+            RenderTarget::Texture(texture) => [texture.width(), texture.height()],
+        }
+    }
+
+    // 当前渲染目标的颜色格式
+    fn target_format(&self) -> TextureFormat {
+        match &self.target {
+            RenderTarget::Surface => self.config.format,
+            RenderTarget::Texture(texture) => texture.format(),
+        }
+    }
+
+    /// 非阻塞地轮询设备，处理已完成的工作并触发其回调（例如 `map_async`）。
+    ///
+    /// `render()` 每帧提交后都会调用一次。`PollType::Poll` 只检查已经完成的工作，
+    /// 立即返回；`PollType::Wait` 会阻塞直到已提交的工作全部执行完毕，
+    /// 读回像素等需要立刻拿到结果的操作使用后者，见 `wait_idle`。
+    pub fn poll_maintain(&self) {
+        if let Err(e) = self.context.device.poll(wgpu::PollType::Poll) {
+            eprintln!("Failed to poll device: {e}");
+        }
+    }
+
+    /// 阻塞直到已提交的 GPU 工作全部执行完毕。
+    ///
+    /// 用于让离屏读回和无窗口测试的结果确定，或在退出前确保 GPU 不再使用任何资源。
+    pub fn wait_idle(&self) {
+        if let Err(e) = self.context.device.poll(wgpu::PollType::Wait) {
+            eprintln!("Failed to wait for GPU idle: {e}");
+        }
+    }
+
+    // 提交下一次 render() 要叠加绘制的 egui 数据
+    #[cfg(feature = "egui")]
+    pub(crate) fn set_egui_frame(&mut self, frame: EguiFrame) {
+        self.egui_frame = Some(frame);
+    }
+
+    #[cfg(feature = "egui")]
+    pub(crate) fn max_texture_side(&self) -> usize {
+        self.context.device.limits().max_texture_dimension_2d as usize
+    }
+
+    pub fn render(&mut self) -> Result<(), SurfaceError> {
+        // 调用方没有取走的帧先呈现，否则无法获取下一张交换链纹理
+        if let Some(texture) = self.pending_present.take() {
+            self.present(PresentToken(texture));
+        }
+        self.profiler.end_frame();
+        let _render_scope = self.profiler.scope("render");
+        self.clock.tick();
+
+        expand_for_wireframe(&self.context.device, self.pipeline_key, &mut self.meshes);
+
+        // 纹理目标不需要获取交换链纹理
+        let (surface_texture, view) = match &self.target {
+            RenderTarget::Surface => {
+                let texture = self.surface.get_current_texture()?;
+                let view = texture
+                    .texture
+                    .create_view(&TextureViewDescriptor::default());
+                (Some(texture), view)
+            }
+            RenderTarget::Texture(texture) => {
+                (None, texture.create_view(&TextureViewDescriptor::default()))
+            }
+        };
+
+        let mut encoder = self
+            .context
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        // =================================================================================
+        // 步骤 1.4: 在渲染通道中执行绘制命令
+        // =================================================================================
+        let encode = |color| {
+            if self.pipeline_key.manual_srgb {
+                encode_srgb(color)
+            } else {
+                color
+            }
+        };
+        let clear_color = match self.animated_background {
+            Some(period) => {
+                let hue = (self.clock.elapsed() / period) as f64;
+                hsv_to_rgb(hue, 0.6, 0.4)
+            }
+            None => self.clear_color,
+        };
+
+        // 信箱模式：整个目标先清成黑边颜色，内容区域再用背景绘制填充，
+        // 因为清屏操作不受裁剪矩形限制
+        let letterbox = self.letterbox_rect();
+        let load = if self.clear_on_present_only {
+            LoadOp::Load
+        } else if letterbox.is_some() {
+            LoadOp::Clear(encode(self.letterbox_color))
+        } else {
+            LoadOp::Clear(encode(clear_color))
+        };
+        let background_colors = self
+            .background_gradient
+            .or(letterbox.map(|_| [clear_color; 2]))
+            .map(|colors| colors.map(encode));
+
+        // 背景的管线按目标格式创建，首次使用时才创建
+        if background_colors.is_some() && self.background_painter.is_none() {
+            self.background_painter = Some(BackgroundPainter::new(
+                &self.context.device,
+                self.target_format(),
+                self.depth_format(),
+            ));
+        }
+        let background = background_colors.zip(self.background_painter.as_ref());
+
+        if self.vignette.is_some() && self.vignette_painter.is_none() {
+            self.vignette_painter = Some(VignettePainter::new(
+                &self.context.device,
+                self.target_format(),
+                self.depth_format(),
+            ));
+        }
+        let vignette = self.vignette.zip(self.vignette_painter.as_ref());
+
+        let depth_view = self
+            .depth_texture
+            .create_view(&TextureViewDescriptor::default());
+
+        // 深度预渲染：只写深度，不绑定颜色附件
+        let depth_prepass = self.pipeline_key.depth_prepass;
+        if depth_prepass {
+            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Depth Prepass"),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            if let Some(rect) = letterbox {
+                set_viewport_rect(&mut render_pass, rect);
+            }
+            draw_meshes(
+                &mut render_pass,
+                &self.pipelines,
+                &self.programs,
+                self.prepass_key(),
+                &self.meshes,
+                &self.default_tint,
+            );
+        }
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(Operations {
+                        // 预渲染已写好深度，这里保留
+                        load: if depth_prepass {
+                            LoadOp::Load
+                        } else {
+                            LoadOp::Clear(1.0)
+                        },
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if let Some(rect) = letterbox {
+                set_viewport_rect(&mut render_pass, rect);
+            }
+            if let Some((colors, painter)) = background {
+                painter.paint(&self.context.queue, &mut render_pass, colors);
+            }
+            draw_meshes(
+                &mut render_pass,
+                &self.pipelines,
+                &self.programs,
+                self.pipeline_key,
+                &self.meshes,
+                &self.default_tint,
+            );
+            if let Some((params, painter)) = vignette {
+                painter.paint(&self.context.queue, &mut render_pass, params);
+            }
+        }
+
+        // 深度可视化覆盖场景，纹理绘制和 UI 仍画在其上
+        if self.depth_debug {
+            let format = self.target_format();
+            let manual_srgb = self.pipeline_key.manual_srgb;
+            let painter = self.depth_debug_painter.get_or_insert_with(|| {
+                DepthDebugPainter::new(&self.context.device, format, manual_srgb)
+            });
+            painter.paint(
+                &self.context.device,
+                &mut encoder,
+                &view,
+                &self.depth_texture,
+            );
+        }
+
+        // 调试用的纹理绘制叠加在场景之上
+        if !self.blit_draws.is_empty() {
+            let size = self.target_size();
+            let format = self.target_format();
+            let manual_srgb = self.pipeline_key.manual_srgb;
+            let painter = self
+                .blit_painter
+                .get_or_insert_with(|| BlitPainter::new(&self.context.device, format, manual_srgb));
+            painter.paint(
+                &self.context.device,
+                &mut encoder,
+                &view,
+                size,
+                &self.blit_draws,
+            );
+            self.blit_draws.clear();
+        }
+
+        // egui 在场景之后绘制，叠加在最上层
+        #[cfg(feature = "egui")]
+        if let Some(frame) = self.egui_frame.take() {
+            let size = self.target_size();
+            let format = self.target_format();
+            let painter = self
+                .egui_painter
+                .get_or_insert_with(|| EguiPainter::new(&self.context.device, format));
+            painter.paint(
+                &self.context.device,
+                &self.context.queue,
+                &mut encoder,
+                &view,
+                size,
+                frame,
+            );
+        }
+
+        self.context.queue.submit(once(encoder.finish()));
+        // 部分后端只有轮询设备时才会触发缓冲区映射等回调
+        self.poll_maintain();
+        if let Some(texture) = surface_texture {
+            if self.present_on_render {
+                self.present(PresentToken(texture));
+            } else {
+                self.pending_present = Some(texture);
+            }
+        }
+        Ok(())
+    }
+}
+
+// 把视口和裁剪矩形都限制在 rect 内
+fn set_viewport_rect(render_pass: &mut RenderPass, [x, y, width, height]: [u32; 4]) {
+    render_pass.set_viewport(x as f32, y as f32, width as f32, height as f32, 0.0, 1.0);
+    render_pass.set_scissor_rect(x, y, width, height);
+}
+
+// 内置程序：position + color 顶点布局，句柄为 PipelineHandle::default()
+fn builtin_program(device: &wgpu::Device, tint_layout: &BindGroupLayout) -> Program {
+    // 加载 WGSL 着色器代码
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Shader"),
+        source: wgpu::ShaderSource::Wgsl(
+            concat!(include_str!("srgb.wgsl"), include_str!("shader.wgsl")).into(),
+        ),
+    });
+
+    // 创建渲染管线布局
+    let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Render Pipeline Layout"),
+        bind_group_layouts: &[tint_layout],
+        push_constant_ranges: &[],
+    });
+
+    Program {
+        label: "Render Pipeline".to_owned(),
+        shader,
+        layout: render_pipeline_layout,
+        vertex_layout: Vertex::desc(),
+        constants: BTreeMap::new(),
+        bind_groups: Vec::new(),
+        source_path: None,
+    }
+}
+
+// 默认场景中的正方形
+fn default_mesh(device: &wgpu::Device) -> Mesh {
+    Mesh::new(
+        device,
+        PipelineHandle::default(),
+        bytemuck::cast_slice(VERTICES),
+        INDICES,
+    )
+}
+
+// 在 [宽, 高] 的目标中居中放置宽高比为 `aspect` 的内容，返回 [x, y, 宽, 高]
+fn letterbox_rect(aspect: f32, [width, height]: [u32; 2]) -> [u32; 4] {
+    let (w, h) = (width as f32, height as f32);
+    let (content_w, content_h) = if w / h > aspect {
+        (h * aspect, h) // 窗口更宽：左右留边
+    } else {
+        (w, w / aspect) // 窗口更高：上下留边
+    };
+    let x = ((w - content_w) / 2.0) as u32;
+    let y = ((h - content_h) / 2.0) as u32;
+    [
+        x,
+        y,
+        (content_w.round() as u32).clamp(1, width - x),
+        (content_h.round() as u32).clamp(1, height - y),
+    ]
+}
+
+// 为实际使用线框管线的网格创建展开后的顶点缓冲区；
+// 带状拓扑和自定义程序不使用线框，不需要展开
+fn expand_for_wireframe(device: &wgpu::Device, key: PipelineKey, meshes: &mut [Mesh]) {
+    for mesh in meshes {
+        if key.for_program(mesh.program).wireframe.is_some() {
+            mesh.ensure_expanded(device, size_of::<Vertex>());
+        }
+    }
+}
+
+// 按网格所属的着色器程序切换管线和绑定组并绘制
+fn draw_meshes(
+    render_pass: &mut RenderPass,
+    pipelines: &HashMap<PipelineKey, RenderPipeline>,
+    programs: &[Program],
+    key: PipelineKey,
+    meshes: &[Mesh],
+    default_tint: &BindGroup,
+) {
+    let mut current = None;
+    for mesh in meshes {
+        let program = &programs[mesh.program.0];
+        // 绑定组没有全部设置时跳过，否则绘制会触发校验错误
+        if program.bind_groups.iter().any(Option::is_none) {
+            continue;
+        }
+        // 没有顶点或索引的网格不绘制，空缓冲区不能绑定
+        if mesh.vertex_data.is_empty() || (mesh.index_buffer.is_some() && mesh.num_indices == 0) {
+            continue;
+        }
+        let key = key.for_program(mesh.program);
+        if current != Some(mesh.program) {
+            // 设置渲染管线
+            render_pass.set_pipeline(&pipelines[&key]);
+            for (index, bind_group) in program.bind_groups.iter().flatten().enumerate() {
+                render_pass.set_bind_group(index as u32, bind_group, &[]);
+            }
+            current = Some(mesh.program);
+        }
+        // 内置程序的 @group(0) 是逐网格的颜色调制，每个网格都要重新绑定；
+        // vs_main 和 vs_wireframe 都会读取它，必须在任何绘制之前设置
+        if mesh.program == PipelineHandle::default() {
+            let tint = mesh.tint.as_ref().map_or(default_tint, |(_, group)| group);
+            render_pass.set_bind_group(0, tint, &[]);
+        }
+        // 线框管线绘制按索引展开后的顶点；没有有效三角形时缓冲区为空，不能绑定
+        if key.wireframe.is_some()
+            && let Some((buffer, count)) = &mesh.expanded
+        {
+            if *count > 0 {
+                render_pass.set_vertex_buffer(0, buffer.slice(..));
+                render_pass.draw(0..*count, 0..1);
+            }
+            continue;
+        }
+        // 设置顶点缓冲区
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        let Some(index_buffer) = &mesh.index_buffer else {
+            // 无索引网格按顶点顺序绘制
+            render_pass.draw(0..mesh.num_vertices, 0..1);
+            continue;
+        };
+        // 设置索引缓冲区
+        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        // 执行绘制！
+        render_pass.draw_indexed(0..mesh.num_indices, 0, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::STRIP_RESTART_INDEX;
+    use crate::pipeline::strip_index_format;
+    use crate::readback::read_texture;
+
+    const SIZE: u32 = 16;
+    const TARGET_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+    // 不依赖窗口的最小场景：与 Renderer 相同的设备、内置程序和默认颜色调制
+    struct Scene {
+        context: DeviceContext,
+        tint_layout: BindGroupLayout,
+        default_tint: BindGroup,
+        programs: Vec<Program>,
+    }
+
+    impl Scene {
+        fn new() -> Self {
+            let context = pollster::block_on(DeviceContext::new());
+            let device = &context.device;
+            let tint_layout = create_tint_layout(device);
+            let (_, default_tint) = create_tint(device, &tint_layout, [1.0; 3]);
+            let programs = vec![builtin_program(device, &tint_layout)];
+            Self {
+                context,
+                tint_layout,
+                default_tint,
+                programs,
+            }
+        }
+
+        fn mesh(&self, vertices: &[Vertex], indices: &[u16]) -> Mesh {
+            Mesh::new(
+                &self.context.device,
+                PipelineHandle::default(),
+                bytemuck::cast_slice(vertices),
+                indices,
+            )
+        }
+
+        // 注册只有 `vs_main`/`fs_main` 的自定义程序（片元输出固定的蓝色），返回使用它的网格
+        fn custom_mesh(&mut self, vertices: &[Vertex]) -> Mesh {
+            let device = &self.context.device;
+            let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Custom Shader"),
+                source: wgpu::ShaderSource::Wgsl(
+                    "@vertex fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+                         return vec4<f32>(position, 1.0);
+                     }
+                     @fragment fn fs_main() -> @location(0) vec4<f32> {
+                         return vec4<f32>(0.0, 0.0, 1.0, 1.0);
+                     }"
+                    .into(),
+                ),
+            });
+            let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Custom Layout"),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+            self.programs.push(Program {
+                label: "Custom".to_owned(),
+                shader,
+                layout,
+                vertex_layout: Vertex::desc(),
+                constants: BTreeMap::new(),
+                bind_groups: Vec::new(),
+                source_path: None,
+            });
+            let indices: Vec<u16> = (0..vertices.len() as u16).collect();
+            Mesh::new(
+                device,
+                PipelineHandle(self.programs.len() - 1),
+                bytemuck::cast_slice(vertices),
+                &indices,
+            )
+        }
+
+        fn render(&self, key: PipelineKey, meshes: &mut [Mesh]) -> Vec<u8> {
+            self.render_to(TARGET_FORMAT, key, meshes)
+        }
+
+        // 把网格离屏绘制到 SIZE×SIZE 的目标上并读回 RGBA 像素，出现校验错误时 panic
+        fn render_to(
+            &self,
+            format: TextureFormat,
+            key: PipelineKey,
+            meshes: &mut [Mesh],
+        ) -> Vec<u8> {
+            let device = &self.context.device;
+            let queue = &self.context.queue;
+            expand_for_wireframe(device, key, meshes);
+            let mut pipelines = HashMap::new();
+            for mesh in meshes.iter() {
+                let key = key.for_program(mesh.program);
+                pipelines.entry(key).or_insert_with(|| {
+                    create_render_pipeline(device, &self.programs[key.program.0], format, key)
+                });
+            }
+
+            let target = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Test Target"),
+                size: wgpu::Extent3d {
+                    width: SIZE,
+                    height: SIZE,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let view = target.create_view(&TextureViewDescriptor::default());
+            let depth_texture = create_depth_texture(device, [SIZE, SIZE], key.depth_format);
+            let depth_view = depth_texture.create_view(&TextureViewDescriptor::default());
+
+            device.push_error_scope(wgpu::ErrorFilter::Validation);
+            let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+            {
+                let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("Test Pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Clear(Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &depth_view,
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Clear(1.0),
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                draw_meshes(
+                    &mut render_pass,
+                    &pipelines,
+                    &self.programs,
+                    key,
+                    meshes,
+                    &self.default_tint,
+                );
+            }
+            queue.submit(once(encoder.finish()));
+            if let Some(e) = pollster::block_on(device.pop_error_scope()) {
+                panic!("validation error: {e}");
+            }
+            read_texture(device, queue, &target)
+        }
+    }
+
+    // 覆盖整个目标的逆时针大三角形
+    fn fullscreen_triangle(color: [f32; 3]) -> [Vertex; 3] {
+        [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]].map(|[x, y]| Vertex {
+            position: [x, y, 0.5],
+            color,
+        })
+    }
+
+    fn pixel(pixels: &[u8], [x, y]: [u32; 2]) -> [u8; 4] {
+        let start = ((y * SIZE + x) * 4) as usize;
+        pixels[start..start + 4].try_into().unwrap()
+    }
+
+    // 竖直的矩形条带，从 x0 到 x1 覆盖整个高度
+    fn strip(x0: f32, x1: f32, color: [f32; 3]) -> [Vertex; 4] {
+        [[x0, -1.0], [x1, -1.0], [x0, 1.0], [x1, 1.0]].map(|[x, y]| Vertex {
+            position: [x, y, 0.5],
+            color,
+        })
+    }
+
+    #[test]
+    fn two_strips_with_wireframe_key() {
+        let scene = Scene::new();
+        let red = [1.0, 0.0, 0.0];
+        let blue = [0.0, 0.0, 1.0];
+        let vertices: Vec<Vertex> = strip(-1.0, -0.5, red)
+            .into_iter()
+            .chain(strip(0.5, 1.0, blue))
+            .collect();
+        let indices = [0, 1, 2, 3, STRIP_RESTART_INDEX, 4, 5, 6, 7];
+        let mut meshes = [scene.mesh(&vertices, &indices)];
+        // 线框只支持三角形列表，带状拓扑下照常绘制，也不展开顶点
+        let key = PipelineKey {
+            topology: PrimitiveTopology::TriangleStrip,
+            wireframe: Some(Wireframe::new(1.0, [1.0; 3])),
+            ..PipelineKey::default()
+        };
+        let program_key = key.for_program(PipelineHandle::default());
+        assert_eq!(program_key.wireframe, None);
+        assert_eq!(
+            strip_index_format(program_key.topology),
+            Some(wgpu::IndexFormat::Uint16)
+        );
+        let pixels = scene.render(key, &mut meshes);
+        assert!(meshes[0].expanded.is_none());
+        // GL 后端不一定支持图元重启，第二条条带的像素因后端而异，只检查第一条
+        assert_eq!(pixel(&pixels, [1, SIZE / 2]), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn wireframe_skips_out_of_range_indices() {
+        let scene = Scene::new();
+        let vertices = fullscreen_triangle([0.0, 1.0, 0.0]);
+        let mut meshes = [
+            scene.mesh(&vertices, &[0, 1, 2, 0, 1, STRIP_RESTART_INDEX, 2, 3, 1]),
+            scene.mesh(&vertices, &[0, 1, STRIP_RESTART_INDEX]),
+        ];
+        let key = PipelineKey {
+            wireframe: Some(Wireframe::new(1.0, [1.0; 3])),
+            ..PipelineKey::default()
+        };
+        let pixels = scene.render(key, &mut meshes);
+        assert_eq!(
+            meshes[0].expanded.as_ref().map(|(_, count)| *count),
+            Some(3)
+        );
+        assert_eq!(
+            meshes[1].expanded.as_ref().map(|(_, count)| *count),
+            Some(0)
+        );
+        assert_eq!(pixel(&pixels, [SIZE / 2, SIZE / 2]), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn wireframe_applies_mesh_tint() {
+        let scene = Scene::new();
+        let mut mesh = scene.mesh(&fullscreen_triangle([1.0; 3]), &[0, 1, 2]);
+        mesh.set_tint(
+            &scene.context.device,
+            &scene.context.queue,
+            &scene.tint_layout,
+            Some([0.0, 1.0, 0.0]),
+        );
+        let key = PipelineKey {
+            wireframe: Some(Wireframe::new(1.0, [1.0, 0.0, 0.0])),
+            ..PipelineKey::default()
+        };
+        let pixels = scene.render(key, &mut [mesh]);
+        // 中心远离三角形的边，只有经过调制的顶点颜色
+        assert_eq!(pixel(&pixels, [SIZE / 2, SIZE / 2]), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn manual_srgb_matches_srgb_target() {
+        let scene = Scene::new();
+        let mut meshes = [scene.mesh(&fullscreen_triangle([0.2, 0.5, 0.8]), &[0, 1, 2])];
+        let center = [SIZE / 2, SIZE / 2];
+
+        // 硬件编码：sRGB 目标，着色器输出线性颜色
+        let srgb = scene.render_to(
+            TextureFormat::Rgba8UnormSrgb,
+            PipelineKey::default(),
+            &mut meshes,
+        );
+        // 手动编码：非 sRGB 目标，着色器中做 sRGB 编码（GL 后端的做法）
+        let manual_key = PipelineKey {
+            manual_srgb: true,
+            ..PipelineKey::default()
+        };
+        let manual = scene.render_to(TextureFormat::Rgba8Unorm, manual_key, &mut meshes);
+
+        let expected = pixel(&srgb, center);
+        let actual = pixel(&manual, center);
+        for (e, a) in expected.into_iter().zip(actual) {
+            assert!(e.abs_diff(a) <= 1, "{expected:?} != {actual:?}");
+        }
+        // 确认确实经过了编码，而不是两边都输出线性值
+        let linear = Color {
+            r: 0.2,
+            g: 0.5,
+            b: 0.8,
+            a: 1.0,
+        };
+        let encoded = encode_srgb(linear);
+        let encoded = [encoded.r, encoded.g, encoded.b].map(|c| (c * 255.0).round() as u8);
+        for (e, a) in encoded.into_iter().zip(actual) {
+            assert!(e.abs_diff(a) <= 1, "{encoded:?} != {actual:?}");
+        }
+    }
+
+    #[test]
+    fn debug_views_skip_custom_programs() {
+        let mut scene = Scene::new();
+        let mut meshes = [scene.custom_mesh(&fullscreen_triangle([1.0; 3]))];
+        // 自定义程序没有调试入口，照常用 fs_main 绘制
+        for key in [
+            PipelineKey {
+                backface_debug: true,
+                ..PipelineKey::default()
+            },
+            PipelineKey {
+                face_debug: true,
+                ..PipelineKey::default()
+            },
+        ] {
+            let pixels = scene.render(key, &mut meshes);
+            assert_eq!(pixel(&pixels, [SIZE / 2, SIZE / 2]), [0, 0, 255, 255]);
+        }
+    }
+
+    #[test]
+    fn empty_meshes_are_skipped() {
+        let scene = Scene::new();
+        let device = &scene.context.device;
+        let mut meshes = [
+            Mesh::new_non_indexed(device, PipelineHandle::default(), &[], size_of::<Vertex>()),
+            scene.mesh(&fullscreen_triangle([1.0; 3]), &[]),
+            scene.mesh(&[], &[]),
+            scene.mesh(&fullscreen_triangle([0.0, 1.0, 0.0]), &[0, 1, 2]),
+        ];
+        let pixels = scene.render(PipelineKey::default(), &mut meshes);
+        assert_eq!(pixel(&pixels, [SIZE / 2, SIZE / 2]), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn deduplicated_empty_input_draws_nothing() {
+        let scene = Scene::new();
+        let (unique, indices) = deduplicate_vertices(&[]);
+        assert!(unique.is_empty() && indices.is_empty());
+        let mut meshes = [scene.mesh(&unique, &indices)];
+        let pixels = scene.render(PipelineKey::default(), &mut meshes);
+        assert_eq!(pixel(&pixels, [SIZE / 2, SIZE / 2]), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn letterbox_centers_content() {
+        // 窗口更宽时左右留边，更高时上下留边，比例一致时铺满
+        assert_eq!(letterbox_rect(1.0, [200, 100]), [50, 0, 100, 100]);
+        assert_eq!(letterbox_rect(1.0, [100, 200]), [0, 50, 100, 100]);
+        assert_eq!(letterbox_rect(16.0 / 9.0, [1920, 1080]), [0, 0, 1920, 1080]);
+        // 极端比例下内容至少保留 1 像素
+        assert_eq!(letterbox_rect(1000.0, [10, 10]), [0, 4, 10, 1]);
+    }
+}
@@ -1,4 +1,5 @@
 // 步骤 1.5: 编写 WGSL 着色器
+// 编译时会在前面拼接 srgb.wgsl，作为 set_shader 的模板时需要一并复制
 
 // 顶点着色器的输入，对应 Rust 中的 Vertex 结构体
 struct VertexInput {
@@ -14,6 +15,19 @@ struct VertexOutput {
     @location(0) color: vec3<f32>,
 };
 
+// 逐网格的颜色调制，与顶点颜色相乘，默认为白色（不改变颜色）
+@group(0) @binding(0) var<uniform> mesh_tint: vec4<f32>;
+
+// MANUAL_SRGB 和 srgb_from_linear 来自编译时拼接在前面的 srgb.wgsl
+
+// 所有片元着色器的最终输出都经过这里
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if MANUAL_SRGB {
+        return vec4<f32>(srgb_from_linear(color.rgb), color.a);
+    }
+    return color;
+}
+
 // 顶点着色器主函数
 @vertex
 fn vs_main(
@@ -21,7 +35,7 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
     out.clip_position = vec4<f32>(model.position, 1.0);
-    out.color = model.color;
+    out.color = model.color * mesh_tint.rgb;
     return out;
 }
 
@@ -31,5 +45,57 @@ fn vs_main(
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // 直接输出插值后的颜色，alpha 为 1.0 (不透明)
-    return vec4<f32>(in.color, 1.0);
-}
\ No newline at end of file
+    return encode_output(vec4<f32>(in.color, 1.0));
+}
+
+// 调试用片元着色器：背面染成品红色，用于发现环绕方向反了的三角形
+// 使用时管线需关闭背面剔除
+@fragment
+fn fs_backface_debug(in: VertexOutput, @builtin(front_facing) front_facing: bool) -> @location(0) vec4<f32> {
+    if front_facing {
+        return encode_output(vec4<f32>(in.color, 1.0));
+    }
+    return encode_output(vec4<f32>(1.0, 0.0, 1.0, 1.0));
+}
+
+// 重心坐标线框：网格按三角形展开为无索引顶点，第 i 个顶点取第 (i % 3) 个角
+override WIREFRAME_THICKNESS: f32 = 1.0; // 线宽，单位为像素
+override WIREFRAME_R: f32 = 1.0;
+override WIREFRAME_G: f32 = 1.0;
+override WIREFRAME_B: f32 = 1.0;
+
+struct WireframeOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    @location(1) barycentric: vec3<f32>,
+};
+
+@vertex
+fn vs_wireframe(model: VertexInput, @builtin(vertex_index) index: u32) -> WireframeOutput {
+    var out: WireframeOutput;
+    out.clip_position = vec4<f32>(model.position, 1.0);
+    out.color = model.color * mesh_tint.rgb;
+    let corner = index % 3u;
+    out.barycentric = vec3<f32>(f32(corner == 0u), f32(corner == 1u), f32(corner == 2u));
+    return out;
+}
+
+// 任一重心坐标分量接近 0 时位于边上；fwidth 把线宽换算成像素并留出 1 像素的抗锯齿过渡
+@fragment
+fn fs_wireframe(in: WireframeOutput) -> @location(0) vec4<f32> {
+    let width = fwidth(in.barycentric);
+    let edge = smoothstep(width * (WIREFRAME_THICKNESS - 1.0), width * WIREFRAME_THICKNESS, in.barycentric);
+    let coverage = 1.0 - min(min(edge.x, edge.y), edge.z);
+    let wire_color = vec3<f32>(WIREFRAME_R, WIREFRAME_G, WIREFRAME_B);
+    return encode_output(vec4<f32>(mix(in.color, wire_color, coverage), 1.0));
+}
+
+// 调试用片元着色器：正面染成绿色、背面染成红色，覆盖原有的顶点颜色
+// 使用时管线需关闭背面剔除
+@fragment
+fn fs_face_debug(in: VertexOutput, @builtin(front_facing) front_facing: bool) -> @location(0) vec4<f32> {
+    if front_facing {
+        return encode_output(vec4<f32>(0.0, 1.0, 0.0, 1.0));
+    }
+    return encode_output(vec4<f32>(1.0, 0.0, 0.0, 1.0));
+}
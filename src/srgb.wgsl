@@ -0,0 +1,12 @@
+// 手动 sRGB 编码，编译时拼接在需要它的着色器前面
+
+// 为 true 时在着色器中手动做 sRGB 编码（表面为非 sRGB 格式时由 Renderer 设置）
+override MANUAL_SRGB: bool = false;
+
+fn srgb_from_linear(rgb: vec3<f32>) -> vec3<f32> {
+    let cutoff = rgb < vec3<f32>(0.0031308);
+    let lower = rgb * vec3<f32>(12.92);
+    let higher = vec3<f32>(1.055) * pow(rgb, vec3<f32>(1.0 / 2.4)) - vec3<f32>(0.055);
+    return select(higher, lower, cutoff);
+}
+
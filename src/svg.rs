@@ -0,0 +1,110 @@
+use std::{fmt, io, path::Path};
+
+use resvg::{tiny_skia, usvg};
+use wgpu::{Device, Queue, Texture, TextureFormat, TextureUsages};
+
+/// `Renderer::load_svg` 的错误
+#[derive(Debug)]
+pub enum SvgError {
+    /// 读取文件失败
+    Io(io::Error),
+    /// 文件不是有效的 SVG
+    Parse(usvg::Error),
+    /// 请求的尺寸为 0 或超出设备支持的最大纹理尺寸
+    InvalidSize(u32),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read svg: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse svg: {e}"),
+            Self::InvalidSize(size) => write!(f, "invalid svg raster size: {size}"),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            Self::InvalidSize(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SvgError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<usvg::Error> for SvgError {
+    fn from(e: usvg::Error) -> Self {
+        Self::Parse(e)
+    }
+}
+
+// 把 SVG 文件栅格化为纹理，较长的一边缩放到 `size` 像素，保持宽高比。
+// 纹理为 Rgba8UnormSrgb、非预乘 alpha，可直接交给 draw_textured_rect
+pub(crate) fn rasterize(
+    device: &Device,
+    queue: &Queue,
+    path: &Path,
+    size: u32,
+) -> Result<Texture, SvgError> {
+    if size == 0 || size > device.limits().max_texture_dimension_2d {
+        return Err(SvgError::InvalidSize(size));
+    }
+    let data = std::fs::read(path)?;
+    let tree = usvg::Tree::from_data(&data, &usvg::Options::default())?;
+
+    let svg_size = tree.size();
+    let scale = size as f32 / svg_size.width().max(svg_size.height());
+    let width = ((svg_size.width() * scale).round() as u32).clamp(1, size);
+    let height = ((svg_size.height() * scale).round() as u32).clamp(1, size);
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or(SvgError::InvalidSize(size))?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    // tiny-skia 输出预乘 alpha，而纹理绘制按非预乘 alpha 混合
+    let pixels: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let c = pixel.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+
+    let extent = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("SVG Texture"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8UnormSrgb,
+        usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        texture.as_image_copy(),
+        &pixels,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(width * 4),
+            rows_per_image: Some(height),
+        },
+        extent,
+    );
+    Ok(texture)
+}
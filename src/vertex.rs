@@ -0,0 +1,58 @@
+use bytemuck::{Pod, Zeroable}; // <-- 引入 bytemuck
+
+// =================================================================================
+// 步骤 1.1: 定义顶点结构体
+// =================================================================================
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct Vertex {
+    pub position: [f32; 3], // 从 2D -> 3D，为了着色器中的 vec3
+    pub color: [f32; 3],
+}
+
+impl Vertex {
+    // 描述顶点在内存中的布局，以便 wgpu 正确读取
+    pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0, // 对应着色器中的 @location(0)
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                    shader_location: 1, // 对应着色器中的 @location(1)
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+            ],
+        }
+    }
+}
+
+// 定义正方形的顶点和索引
+pub(crate) const VERTICES: &[Vertex] = &[
+    Vertex {
+        position: [-0.5, 0.5, 0.0],
+        color: [1.0, 0.0, 0.0],
+    }, // 左上, 红色
+    Vertex {
+        position: [-0.5, -0.5, 0.0],
+        color: [0.0, 1.0, 0.0],
+    }, // 左下, 绿色
+    Vertex {
+        position: [0.5, -0.5, 0.0],
+        color: [0.0, 0.0, 1.0],
+    }, // 右下, 蓝色
+    Vertex {
+        position: [0.5, 0.5, 0.0],
+        color: [1.0, 1.0, 0.0],
+    }, // 右上, 黄色
+];
+
+pub(crate) const INDICES: &[u16] = &[
+    0, 1, 2, // 第一个三角形
+    0, 2, 3, // 第二个三角形
+];
@@ -0,0 +1,49 @@
+use wgpu::{Device, Queue, RenderPass, TextureFormat};
+
+use crate::fullscreen::FullscreenPainter;
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Vignette {
+    intensity: f32,
+    radius: f32,
+    // uniform 缓冲区按 16 字节对齐
+    _padding: [f32; 2],
+}
+
+// 在场景之后把画面边角压暗，不测试也不写入深度
+pub(crate) struct VignettePainter(FullscreenPainter);
+
+impl VignettePainter {
+    pub(crate) fn new(device: &Device, format: TextureFormat, depth_format: TextureFormat) -> Self {
+        Self(FullscreenPainter::new(
+            device,
+            "Vignette",
+            include_str!("vignette.wgsl"),
+            size_of::<Vignette>(),
+            // 只压暗颜色，不改变目标的 alpha
+            wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::COLOR,
+            },
+            depth_format,
+        ))
+    }
+
+    // 在渲染通道的最后调用，覆盖当前视口
+    pub(crate) fn paint(
+        &self,
+        queue: &Queue,
+        render_pass: &mut RenderPass,
+        [intensity, radius]: [f32; 2],
+    ) {
+        let vignette = Vignette {
+            intensity,
+            radius,
+            _padding: [0.0; 2],
+        };
+        self.0
+            .paint(queue, render_pass, bytemuck::bytes_of(&vignette));
+    }
+}
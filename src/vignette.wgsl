@@ -0,0 +1,31 @@
+// 暗角：在场景之上叠加一个全屏三角形，按到画面中心的距离把边角压暗
+
+struct Vignette {
+    intensity: f32, // 角落处的最大压暗程度，0 为关闭，1 为全黑
+    radius: f32,    // 开始压暗的距离，0 为中心，1 为角落
+};
+
+@group(0) @binding(0) var<uniform> vignette: Vignette;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // 归一化到中心为 0、四个角为 1
+    let distance = length(in.uv - vec2<f32>(0.5)) * sqrt(2.0);
+    let falloff = smoothstep(vignette.radius, 1.0, distance);
+    // 输出黑色，用 alpha 控制压暗程度
+    return vec4<f32>(0.0, 0.0, 0.0, falloff * vignette.intensity);
+}